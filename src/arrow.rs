@@ -0,0 +1,302 @@
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The shape drawn at the end of an edge, used by `arrowhead` and `arrowtail`
+///
+/// An arrow shape stacks up to four arrows, starting at the node and working outwards, so
+/// `lteeoldiamond` is a left half tee followed by an open left half diamond. Stacks are built
+/// through `ArrowShape::new` or parsing, which both validate the modifiers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrowShape {
+    arrows: Vec<Arrow>,
+}
+
+/// A single primitive shape in an `ArrowShape`, with its modifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arrow {
+    pub primitive: ArrowPrimitive,
+
+    /// Draw the shape outlined instead of filled (the `o` modifier)
+    pub open: bool,
+
+    /// Only draw half of the shape (the `l` and `r` modifiers)
+    pub side: Option<ArrowSide>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrowPrimitive {
+    Box,
+    Crow,
+    Curve,
+    ICurve,
+    Diamond,
+    Dot,
+    Inv,
+    None,
+    Normal,
+    Tee,
+    Vee,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrowSide {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ArrowError {
+    #[error("An arrow shape needs at least one arrow")]
+    Empty,
+
+    #[error("An arrow shape can stack at most {MAX_ARROWS} arrows, found {0}")]
+    TooManyArrows(usize),
+
+    #[error("The `{0}` modifier can't be applied to `{1}`")]
+    InvalidModifier(char, &'static str),
+
+    #[error("Unknown arrow shape `{0}`")]
+    UnknownShape(String),
+}
+
+/// Graphviz ignores any arrows beyond the fourth
+const MAX_ARROWS: usize = 4;
+
+impl ArrowShape {
+    pub fn new<I>(arrows: I) -> Result<Self, ArrowError>
+    where
+        I: IntoIterator,
+        I::Item: Into<Arrow>,
+    {
+        let arrows: Vec<Arrow> = arrows.into_iter().map(Into::into).collect();
+
+        if arrows.is_empty() {
+            return Err(ArrowError::Empty);
+        }
+
+        if arrows.len() > MAX_ARROWS {
+            return Err(ArrowError::TooManyArrows(arrows.len()));
+        }
+
+        for arrow in &arrows {
+            arrow.validate()?;
+        }
+
+        Ok(Self { arrows })
+    }
+
+    pub fn arrows(&self) -> &[Arrow] {
+        &self.arrows
+    }
+
+    pub fn as_string(&self) -> String {
+        let mut string = String::new();
+        for arrow in &self.arrows {
+            if arrow.open {
+                string.push('o');
+            }
+
+            match arrow.side {
+                Some(ArrowSide::Left) => string.push('l'),
+                Some(ArrowSide::Right) => string.push('r'),
+                None => {}
+            }
+
+            string.push_str(arrow.primitive.as_str());
+        }
+        string
+    }
+}
+
+impl From<ArrowPrimitive> for ArrowShape {
+    fn from(primitive: ArrowPrimitive) -> Self {
+        Self {
+            arrows: vec![Arrow::new(primitive)],
+        }
+    }
+}
+
+impl FromStr for ArrowShape {
+    type Err = ArrowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut arrows = Vec::new();
+        let mut rest = s;
+
+        while !rest.is_empty() {
+            let mut arrow = Arrow::new(ArrowPrimitive::None);
+
+            // No primitive starts with a modifier letter, so they can be stripped greedily
+            if let Some(stripped) = rest.strip_prefix('o') {
+                arrow.open = true;
+                rest = stripped;
+            }
+
+            if let Some(stripped) = rest.strip_prefix('l') {
+                arrow.side = Some(ArrowSide::Left);
+                rest = stripped;
+            } else if let Some(stripped) = rest.strip_prefix('r') {
+                arrow.side = Some(ArrowSide::Right);
+                rest = stripped;
+            }
+
+            let primitive = ArrowPrimitive::ALL
+                .into_iter()
+                .find(|primitive| rest.starts_with(primitive.as_str()))
+                .ok_or_else(|| ArrowError::UnknownShape(s.to_string()))?;
+
+            arrow.primitive = primitive;
+            rest = &rest[primitive.as_str().len()..];
+            arrows.push(arrow);
+        }
+
+        Self::new(arrows)
+    }
+}
+
+impl Arrow {
+    pub fn new(primitive: ArrowPrimitive) -> Self {
+        Self {
+            primitive,
+            open: false,
+            side: None,
+        }
+    }
+
+    pub fn open(mut self) -> Self {
+        self.open = true;
+        self
+    }
+
+    pub fn left(mut self) -> Self {
+        self.side = Some(ArrowSide::Left);
+        self
+    }
+
+    pub fn right(mut self) -> Self {
+        self.side = Some(ArrowSide::Right);
+        self
+    }
+
+    fn validate(&self) -> Result<(), ArrowError> {
+        use ArrowPrimitive as P;
+
+        // Only the shapes Graphviz fills can be drawn open, including `vee`, an inverted `crow`
+        let fillable = matches!(
+            self.primitive,
+            P::Box | P::Crow | P::Diamond | P::Dot | P::Inv | P::Normal | P::Vee
+        );
+        if self.open && !fillable {
+            return Err(ArrowError::InvalidModifier('o', self.primitive.as_str()));
+        }
+
+        // A dot has no halves, and there's nothing to halve about `none`
+        if let (Some(side), P::Dot | P::None) = (self.side, self.primitive) {
+            let modifier = match side {
+                ArrowSide::Left => 'l',
+                ArrowSide::Right => 'r',
+            };
+            return Err(ArrowError::InvalidModifier(
+                modifier,
+                self.primitive.as_str(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl From<ArrowPrimitive> for Arrow {
+    fn from(primitive: ArrowPrimitive) -> Self {
+        Self::new(primitive)
+    }
+}
+
+impl ArrowPrimitive {
+    const ALL: [Self; 11] = [
+        Self::Box,
+        Self::Crow,
+        Self::Curve,
+        Self::ICurve,
+        Self::Diamond,
+        Self::Dot,
+        Self::Inv,
+        Self::None,
+        Self::Normal,
+        Self::Tee,
+        Self::Vee,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Box => "box",
+            Self::Crow => "crow",
+            Self::Curve => "curve",
+            Self::ICurve => "icurve",
+            Self::Diamond => "diamond",
+            Self::Dot => "dot",
+            Self::Inv => "inv",
+            Self::None => "none",
+            Self::Normal => "normal",
+            Self::Tee => "tee",
+            Self::Vee => "vee",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_string() {
+        let shape = ArrowShape::new([
+            Arrow::new(ArrowPrimitive::Tee).left(),
+            Arrow::new(ArrowPrimitive::Diamond).open().left(),
+        ])
+        .unwrap();
+        assert_eq!(shape.as_string(), "lteeoldiamond");
+
+        let shape = ArrowShape::from(ArrowPrimitive::ICurve);
+        assert_eq!(shape.as_string(), "icurve");
+    }
+
+    #[test]
+    fn parse() {
+        let shape: ArrowShape = "odotinvrvee".parse().unwrap();
+        assert_eq!(
+            shape.arrows(),
+            [
+                Arrow::new(ArrowPrimitive::Dot).open(),
+                Arrow::new(ArrowPrimitive::Inv),
+                Arrow::new(ArrowPrimitive::Vee).right(),
+            ]
+        );
+        assert_eq!(shape.as_string(), "odotinvrvee");
+
+        let shape: ArrowShape = "ovee".parse().unwrap();
+        assert_eq!(shape.arrows(), [Arrow::new(ArrowPrimitive::Vee).open()]);
+
+        assert_eq!(
+            "arrow".parse::<ArrowShape>(),
+            Err(ArrowError::UnknownShape("arrow".into()))
+        );
+        assert_eq!("".parse::<ArrowShape>(), Err(ArrowError::Empty));
+    }
+
+    #[test]
+    fn validation() {
+        assert_eq!(
+            "normalnormalnormalnormalnormal".parse::<ArrowShape>(),
+            Err(ArrowError::TooManyArrows(5))
+        );
+        assert_eq!(
+            "ldot".parse::<ArrowShape>(),
+            Err(ArrowError::InvalidModifier('l', "dot"))
+        );
+        assert_eq!(
+            ArrowShape::new([Arrow::new(ArrowPrimitive::Tee).open()]),
+            Err(ArrowError::InvalidModifier('o', "tee"))
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use crate::{
+    lexer::Id,
+    utils::{bool_string, esc_string, sanitize},
+    Point, Rect, Unit,
+};
+use std::slice;
+
+/// A single attribute of a node, edge or graph
+///
+/// Implement this to write attributes that ellipsis doesn't know about, and add them through
+/// `Node::attr`, `Edge::attr` or `Graph::attr`.
+///
+/// `pair` returns the attribute's key and its value exactly as it should appear in the DOT
+/// output, so the implementation is responsible for quoting the value. Keys are quoted when
+/// written, as the parser accepts any ID as a key. Pass strings through `sanitize`,
+/// which leaves identifiers and numerals as they are and quotes and escapes everything else.
+/// HTML-like values are the exception: they are written unquoted, between `<` and `>`.
+pub trait Attribute {
+    fn pair(&self) -> (&str, String);
+
+    /// The attribute's key, which lookups in `Attributes` call often, so types whose values are
+    /// costly to format should override it
+    fn key(&self) -> &str {
+        self.pair().0
+    }
+}
+
+/// The value of an attribute that has no typed variant, formatted according to its DOT type
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    Bool(bool),
+    Int(i64),
+
+    /// A double, or a quoted string for NaN and infinities, which Graphviz doesn't read as doubles
+    Double(f64),
+
+    /// A point, written in points like Graphviz's `point` type if it has a unit
+    Point(Point),
+
+    /// A rectangle, written in points like Graphviz's `rect` type if it has a unit
+    Rect(Rect),
+
+    /// Literal text, with any backslashes escaped
+    String(String),
+
+    /// Text in which Graphviz interprets escapes like `\l` and `\N`, written as-is
+    EscString(String),
+
+    /// An HTML-like value, without its outer angle brackets
+    Html(String),
+
+    /// A value that is written exactly as given, without any quoting
+    Raw(String),
+}
+
+impl AttributeValue {
+    pub fn as_string(&self) -> String {
+        match self {
+            Self::Bool(value) => bool_string(*value),
+            Self::Int(value) => format!("{value}"),
+            Self::Double(value) if value.is_finite() => format!("{value}"),
+            Self::Double(value) => format!("\"{value}\""),
+            Self::Point(point) => sanitize(&point.as_string_in(Unit::Points)),
+            Self::Rect(rect) => sanitize(&rect.as_string_in(Unit::Points)),
+            Self::String(string) => sanitize(string),
+            Self::EscString(string) => esc_string(string),
+            Self::Html(html) => format!("<{html}>"),
+            Self::Raw(raw) => raw.clone(),
+        }
+    }
+
+    /// Guess the type of a parsed value, in a way that writes it back the same
+    pub(crate) fn from_id(id: Id) -> Self {
+        match id {
+            Id::Plain(plain) => match plain.as_str() {
+                "true" => Self::Bool(true),
+                "false" => Self::Bool(false),
+                str => {
+                    if let Some(int) = str.parse().ok().filter(|int: &i64| int.to_string() == str) {
+                        Self::Int(int)
+                    } else if let Some(double) = str
+                        .parse()
+                        .ok()
+                        .filter(|double: &f64| double.to_string() == str)
+                    {
+                        Self::Double(double)
+                    } else {
+                        Self::String(plain)
+                    }
+                }
+            },
+            // Quoted IDs keep their backslash escapes, so a value with any is kept as an
+            // escString and written back with the same spelling
+            Id::Quoted(quoted) if quoted.contains('\\') => Self::EscString(quoted),
+            Id::Quoted(quoted) => Self::String(quoted),
+            Id::Html(html) => Self::Html(html),
+        }
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<i32> for AttributeValue {
+    fn from(value: i32) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        Self::Double(value)
+    }
+}
+
+impl From<f32> for AttributeValue {
+    fn from(value: f32) -> Self {
+        Self::Double(value.into())
+    }
+}
+
+impl From<Point> for AttributeValue {
+    fn from(point: Point) -> Self {
+        Self::Point(point)
+    }
+}
+
+impl From<Rect> for AttributeValue {
+    fn from(rect: Rect) -> Self {
+        Self::Rect(rect)
+    }
+}
+
+impl From<&str> for AttributeValue {
+    fn from(string: &str) -> Self {
+        Self::String(string.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(string: String) -> Self {
+        Self::String(string)
+    }
+}
+
+/// The attributes of a node, edge or graph, keyed by their DOT name
+///
+/// Setting an attribute that is already present replaces it in place, so every key appears at
+/// most once, and attributes are written in the order they were first set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attributes<A> {
+    attributes: Vec<A>,
+}
+
+impl<A: Attribute> Attributes<A> {
+    pub fn new() -> Self {
+        Self {
+            attributes: Vec::new(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&A> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.key() == key)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut A> {
+        self.attributes
+            .iter_mut()
+            .find(|attribute| attribute.key() == key)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Add an attribute, returning the one it replaced, if any
+    pub fn set(&mut self, attribute: A) -> Option<A> {
+        match self.get_mut(attribute.key()) {
+            Some(existing) => Some(std::mem::replace(existing, attribute)),
+            None => {
+                self.attributes.push(attribute);
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<A> {
+        let index = self
+            .attributes
+            .iter()
+            .position(|attribute| attribute.key() == key)?;
+        Some(self.attributes.remove(index))
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, A> {
+        self.attributes.iter()
+    }
+
+    pub fn as_slice(&self) -> &[A] {
+        &self.attributes
+    }
+
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+impl<A: Attribute> Default for Attributes<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Attribute> Extend<A> for Attributes<A> {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, attributes: I) {
+        for attribute in attributes {
+            self.set(attribute);
+        }
+    }
+}
+
+impl<A: Attribute> FromIterator<A> for Attributes<A> {
+    fn from_iter<I: IntoIterator<Item = A>>(attributes: I) -> Self {
+        let mut collected = Self::new();
+        collected.extend(attributes);
+        collected
+    }
+}
+
+impl<'a, A> IntoIterator for &'a Attributes<A> {
+    type Item = &'a A;
+    type IntoIter = slice::Iter<'a, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.attributes.iter()
+    }
+}
+
+impl<A> IntoIterator for Attributes<A> {
+    type Item = A;
+    type IntoIter = std::vec::IntoIter<A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.attributes.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EdgeAttribute, GraphAttribute, Label, NodeAttribute, Point, Port};
+
+    #[test]
+    fn set_semantics() {
+        let mut attributes = Attributes::new();
+        attributes.set(NodeAttribute::Label(Label::Text("A".into())));
+        attributes.set(NodeAttribute::Width(2.0));
+        attributes.set(NodeAttribute::Unknown("foo".into(), "bar".into()));
+
+        let replaced = attributes.set(NodeAttribute::Label(Label::Text("B".into())));
+        assert_eq!(
+            replaced,
+            Some(NodeAttribute::Label(Label::Text("A".into())))
+        );
+        assert_eq!(
+            attributes.as_slice(),
+            [
+                NodeAttribute::Label(Label::Text("B".into())),
+                NodeAttribute::Width(2.0),
+                NodeAttribute::Unknown("foo".into(), "bar".into()),
+            ]
+        );
+
+        assert_eq!(attributes.get("width"), Some(&NodeAttribute::Width(2.0)));
+        assert!(attributes.contains("foo"));
+
+        assert_eq!(attributes.remove("width"), Some(NodeAttribute::Width(2.0)));
+        assert_eq!(attributes.remove("width"), None);
+        assert_eq!(attributes.len(), 2);
+    }
+
+    #[test]
+    fn keys_match_pairs() {
+        let nodes = [
+            NodeAttribute::Shape(None),
+            NodeAttribute::Url("u".into()),
+            NodeAttribute::Unknown("foo".into(), "bar".into()),
+        ];
+        for attribute in &nodes {
+            assert_eq!(attribute.key(), attribute.pair().0);
+        }
+
+        let edges = [
+            EdgeAttribute::HeadLp(Point::new(1.0, 2.0)),
+            EdgeAttribute::TailPort(Port::new("p")),
+            EdgeAttribute::Unknown("foo".into(), "bar".into()),
+        ];
+        for attribute in &edges {
+            assert_eq!(attribute.key(), attribute.pair().0);
+        }
+
+        let graphs = [
+            GraphAttribute::Compound(true),
+            GraphAttribute::Url("u".into()),
+            GraphAttribute::Unknown("foo".into(), "bar".into()),
+        ];
+        for attribute in &graphs {
+            assert_eq!(attribute.key(), attribute.pair().0);
+        }
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(AttributeValue::Bool(true).as_string(), "true");
+        assert_eq!(AttributeValue::Int(-3).as_string(), "-3");
+        assert_eq!(AttributeValue::Double(0.25).as_string(), "0.25");
+        assert_eq!(AttributeValue::Double(f64::NAN).as_string(), r#""NaN""#);
+        assert_eq!(
+            AttributeValue::Double(f64::NEG_INFINITY).as_string(),
+            r#""-inf""#
+        );
+        assert_eq!(
+            AttributeValue::Point(Point::new(1.0, 2.5)).as_string(),
+            r#""1,2.5""#
+        );
+        assert_eq!(
+            AttributeValue::Rect(Rect::new((0.0, 0.0), (10.0, 20.0))).as_string(),
+            r#""0,0,10,20""#
+        );
+        assert_eq!(
+            AttributeValue::Point(Point::new(1.0, 0.5).unit(Unit::Inches).fixed()).as_string(),
+            r#""72,36!""#
+        );
+        assert_eq!(
+            AttributeValue::Rect(Rect::new((0.0, 0.0), (2.0, 1.0)).unit(Unit::Inches)).as_string(),
+            r#""0,0,144,72""#
+        );
+        assert_eq!(
+            AttributeValue::String(r"C:\dir".into()).as_string(),
+            r#""C:\\dir""#
+        );
+        assert_eq!(
+            AttributeValue::EscString(r#"\N\l"quoted"\"#.into()).as_string(),
+            r#""\N\l\"quoted\"\\""#
+        );
+        assert_eq!(
+            AttributeValue::Html("<b>bold</b>".into()).as_string(),
+            "<<b>bold</b>>"
+        );
+        assert_eq!(AttributeValue::Raw("a b".into()).as_string(), "a b");
+    }
+
+    #[test]
+    fn values_from_ids() {
+        let value = |id| AttributeValue::from_id(id);
+        assert_eq!(
+            value(Id::Plain("false".into())),
+            AttributeValue::Bool(false)
+        );
+        assert_eq!(value(Id::Plain("42".into())), AttributeValue::Int(42));
+        assert_eq!(value(Id::Plain("1.5".into())), AttributeValue::Double(1.5));
+        assert_eq!(
+            value(Id::Plain("1.50".into())),
+            AttributeValue::String("1.50".into())
+        );
+        assert_eq!(
+            value(Id::Quoted(r"\N".into())),
+            AttributeValue::EscString(r"\N".into())
+        );
+        assert_eq!(
+            value(Id::Quoted(r"C:\\dir".into())).as_string(),
+            r#""C:\\dir""#
+        );
+        assert_eq!(
+            value(Id::Html("<i>x</i>".into())),
+            AttributeValue::Html("<i>x</i>".into())
+        );
+    }
+}
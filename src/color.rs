@@ -1,7 +1,15 @@
+use crate::{
+    color_scheme::{is_transparent, lookup, SVG, X11},
+    lexer::Id,
+    ColorScheme,
+};
 use hex::{FromHex, FromHexError};
-use std::str::FromStr;
+use std::{convert::Infallible, str::FromStr};
 use thiserror::Error;
 
+/// The color Graphviz draws for `transparent`, `none` and `invis`
+const TRANSPARENT: [u8; 4] = [0xFF, 0xFF, 0xFE, 0x00];
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Color {
     Rgba {
@@ -15,7 +23,15 @@ pub enum Color {
         saturation: f32,
         value: f32,
     },
+
+    /// A color name, resolved against the `colorscheme` in effect (X11 by default)
     Name(String),
+
+    /// A color taken from an explicit scheme, written as `/scheme/color`
+    Scheme {
+        scheme: ColorScheme,
+        index_or_name: String,
+    },
 }
 
 impl Color {
@@ -33,8 +49,240 @@ impl Color {
                 value,
             } => format!("{hue} {saturation} {value}"),
             Self::Name(name) => name.clone(),
+            Self::Scheme {
+                scheme,
+                index_or_name,
+            } => format!("/{}/{index_or_name}", scheme.as_string()),
+        }
+    }
+
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::Rgba {
+            red,
+            green,
+            blue,
+            alpha: 0xFF,
+        }
+    }
+
+    /// An RGB color from HSL components, which all range from 0 to 1
+    ///
+    /// Graphviz doesn't understand HSL, so the color is converted up front.
+    pub fn hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let value = lightness + chroma / 2.0;
+        let saturation = if value == 0.0 { 0.0 } else { chroma / value };
+
+        let [red, green, blue] = hsv_to_rgb(hue, saturation, value);
+        Self::rgb(to_byte(red), to_byte(green), to_byte(blue))
+    }
+
+    /// The color's red, green, blue and alpha channels
+    ///
+    /// Names are resolved against the X11 table, or the SVG table for `/svg/` colors. Brewer
+    /// colors and unknown names have no built-in value, and return `None`.
+    pub fn to_rgba(&self) -> Option<[u8; 4]> {
+        let rgb = match self {
+            Self::Rgba {
+                red,
+                green,
+                blue,
+                alpha,
+            } => return Some([*red, *green, *blue, *alpha]),
+            Self::Hsv {
+                hue,
+                saturation,
+                value,
+            } => {
+                let [red, green, blue] = hsv_to_rgb(*hue, *saturation, *value);
+                [to_byte(red), to_byte(green), to_byte(blue)]
+            }
+            Self::Name(name) if is_transparent(name) => return Some(TRANSPARENT),
+            Self::Name(name) => lookup(X11, name)?,
+            Self::Scheme {
+                scheme,
+                index_or_name,
+            } => match scheme {
+                ColorScheme::X11 if is_transparent(index_or_name) => return Some(TRANSPARENT),
+                ColorScheme::X11 => lookup(X11, index_or_name)?,
+                ColorScheme::Svg => lookup(SVG, index_or_name)?,
+                ColorScheme::Brewer { .. } => return None,
+            },
+        };
+
+        Some([rgb[0], rgb[1], rgb[2], 0xFF])
+    }
+
+    /// The color's hue, saturation and value, which all range from 0 to 1
+    pub fn to_hsv(&self) -> Option<[f32; 3]> {
+        if let Self::Hsv {
+            hue,
+            saturation,
+            value,
+        } = self
+        {
+            return Some([*hue, *saturation, *value]);
+        }
+
+        let [red, green, blue] = self.to_unit_rgb()?;
+        let max = red.max(green).max(blue);
+        let chroma = max - red.min(green).min(blue);
+        let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+
+        Some([hue(red, green, blue, max, chroma), saturation, max])
+    }
+
+    /// The color's hue, saturation and lightness, which all range from 0 to 1
+    pub fn to_hsl(&self) -> Option<[f32; 3]> {
+        let [red, green, blue] = self.to_unit_rgb()?;
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let chroma = max - min;
+
+        let lightness = (max + min) / 2.0;
+        let saturation = if lightness == 0.0 || lightness == 1.0 {
+            0.0
+        } else {
+            chroma / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+
+        Some([hue(red, green, blue, max, chroma), saturation, lightness])
+    }
+
+    fn to_unit_rgb(&self) -> Option<[f32; 3]> {
+        let [red, green, blue, _] = self.to_rgba()?;
+        Some([red, green, blue].map(|channel| channel as f32 / 255.0))
+    }
+
+    // --- Manipulation --- //
+    //
+    // These work on the resolved RGB value, so they return `None` for colors that can't be
+    // resolved (see `to_rgba`). The results are always RGBA, and keep the original alpha.
+
+    /// Raise the HSL lightness by `amount`, from 0 to 1
+    pub fn lighten(&self, amount: f32) -> Option<Self> {
+        self.map_hsl(|hue, saturation, lightness| [hue, saturation, lightness + amount])
+    }
+
+    /// Lower the HSL lightness by `amount`, from 0 to 1
+    pub fn darken(&self, amount: f32) -> Option<Self> {
+        self.lighten(-amount)
+    }
+
+    /// Raise the HSL saturation by `amount`, from 0 to 1
+    pub fn saturate(&self, amount: f32) -> Option<Self> {
+        self.map_hsl(|hue, saturation, lightness| [hue, saturation + amount, lightness])
+    }
+
+    /// Lower the HSL saturation by `amount`, from 0 to 1
+    pub fn desaturate(&self, amount: f32) -> Option<Self> {
+        self.saturate(-amount)
+    }
+
+    /// Blend towards `other`, where a `ratio` of 0 is this color and 1 is `other`
+    pub fn mix(&self, other: &Color, ratio: f32) -> Option<Self> {
+        let ratio = ratio.clamp(0.0, 1.0);
+        let from = self.to_rgba()?;
+        let to = other.to_rgba()?;
+
+        let [red, green, blue, alpha] = [0, 1, 2, 3].map(|index| {
+            let channel = from[index] as f32 * (1.0 - ratio) + to[index] as f32 * ratio;
+            channel.round() as u8
+        });
+
+        Some(Self::Rgba {
+            red,
+            green,
+            blue,
+            alpha,
+        })
+    }
+
+    /// The same color with its alpha set to `alpha`, from 0 to 1
+    pub fn with_alpha(&self, alpha: f32) -> Option<Self> {
+        let [red, green, blue, _] = self.to_rgba()?;
+        Some(Self::Rgba {
+            red,
+            green,
+            blue,
+            alpha: to_byte(alpha.clamp(0.0, 1.0)),
+        })
+    }
+
+    /// The relative luminance as defined by WCAG, from 0 for black to 1 for white
+    pub fn relative_luminance(&self) -> Option<f32> {
+        let [red, green, blue] = self.to_unit_rgb()?.map(|channel| {
+            if channel <= 0.04045 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        });
+
+        Some(0.2126 * red + 0.7152 * green + 0.0722 * blue)
+    }
+
+    /// The WCAG contrast ratio between two colors, from 1 to 21
+    pub fn contrast_ratio(&self, other: &Color) -> Option<f32> {
+        let a = self.relative_luminance()?;
+        let b = other.relative_luminance()?;
+        Some((a.max(b) + 0.05) / (a.min(b) + 0.05))
+    }
+
+    /// Black or white, whichever contrasts most with this color when used as a background
+    pub fn readable_text_color(&self) -> Option<Self> {
+        let black = Self::rgb(0, 0, 0);
+        let white = Self::rgb(0xFF, 0xFF, 0xFF);
+
+        if self.contrast_ratio(&black)? >= self.contrast_ratio(&white)? {
+            Some(black)
+        } else {
+            Some(white)
         }
     }
+
+    fn map_hsl(&self, f: impl FnOnce(f32, f32, f32) -> [f32; 3]) -> Option<Self> {
+        let [hue, saturation, lightness] = self.to_hsl()?;
+        let [hue, saturation, lightness] = f(hue, saturation, lightness);
+
+        let [red, green, blue, _] =
+            Self::hsl(hue, saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0)).to_rgba()?;
+
+        let alpha = self.to_rgba()?[3];
+        Some(Self::Rgba {
+            red,
+            green,
+            blue,
+            alpha,
+        })
+    }
+
+    /// A color from `scheme`, checking that the scheme actually contains it
+    pub fn scheme(
+        scheme: ColorScheme,
+        index_or_name: impl Into<String>,
+    ) -> Result<Self, ColorParseError> {
+        let index_or_name = index_or_name.into();
+        if scheme.contains(&index_or_name) {
+            Ok(Self::Scheme {
+                scheme,
+                index_or_name,
+            })
+        } else {
+            Err(ColorParseError::NotInScheme(
+                scheme.as_string(),
+                index_or_name,
+            ))
+        }
+    }
+
+    /// Anything that doesn't parse is taken to be a color name, because it might belong to a
+    /// `colorscheme` set elsewhere
+    pub(crate) fn from_id(id: Id) -> Self {
+        id.as_str()
+            .parse()
+            .unwrap_or_else(|_| Self::Name(id.into_string()))
+    }
 }
 
 #[derive(Debug, Error, PartialEq)]
@@ -47,47 +295,89 @@ pub enum ColorParseError {
 
     #[error("Incorrect float represntations of HSV")]
     InvalidHsv,
+
+    #[error("HSV components must lie between 0 and 1")]
+    HsvOutOfRange,
+
+    #[error("Invalid CSS color `{0}`")]
+    InvalidCss(String),
+
+    #[error("`{0}` is not a color name")]
+    UnknownName(String),
+
+    #[error("Unknown color scheme `{0}`")]
+    UnknownScheme(String),
+
+    #[error("`{1}` is not a color in the `{0}` scheme")]
+    NotInScheme(String, String),
+
+    #[error("A color fraction must be a number between 0 and 1")]
+    InvalidFraction,
+
+    #[error("The fractions in a color list add up to {0}, which is more than 1")]
+    FractionsExceedOne(f32),
+}
+
+/// Lets the fallible builders accept colors that are already parsed
+impl From<Infallible> for ColorParseError {
+    fn from(infallible: Infallible) -> Self {
+        match infallible {}
+    }
 }
 
 impl FromStr for Color {
     type Err = ColorParseError;
 
+    /// Parses the colors Graphviz understands, as well as CSS's `#rgb`, `rgb()` and `hsl()`
+    ///
+    /// Bare names and indices are kept as they are, since they are resolved against whichever
+    /// `colorscheme` is in effect where the color is used.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
         match s.chars().next() {
-            Some('#') if s.len() == 9 => {
-                let hex = <[u8; 4]>::from_hex(&s[1..])?;
-                Ok(Self::Rgba {
-                    red: hex[0],
-                    green: hex[1],
-                    blue: hex[2],
-                    alpha: hex[3],
-                })
-            }
-            Some('#') if s.len() == 7 => {
-                let hex = <[u8; 3]>::from_hex(&s[1..])?;
-                Ok(Self::Rgba {
-                    red: hex[0],
-                    green: hex[1],
-                    blue: hex[2],
-                    alpha: 0xFF,
-                })
+            Some('#') => parse_hex(&s[1..]),
+            Some('/') => {
+                let (scheme, color) = s[1..]
+                    .split_once('/')
+                    .ok_or_else(|| ColorParseError::UnknownName(s.to_string()))?;
+                Self::scheme(scheme.parse()?, color)
             }
+            Some(_) if is_name(s) => Ok(Self::Name(s.to_string())),
+            Some(c) if c.is_alphabetic() => match s.split_once('(') {
+                Some((function, arguments)) => {
+                    let arguments = arguments
+                        .strip_suffix(')')
+                        .ok_or_else(|| ColorParseError::InvalidCss(s.to_string()))?;
+                    parse_css(function, arguments)
+                        .ok_or_else(|| ColorParseError::InvalidCss(s.to_string()))
+                }
+                None => Err(ColorParseError::UnknownName(s.to_string())),
+            },
             Some(_) => {
-                let words = s.split_whitespace().collect::<Vec<&str>>();
-                if words.len() == 3 {
-                    Ok(Self::Hsv {
-                        hue: words[0]
-                            .parse::<f32>()
-                            .map_err(|_| ColorParseError::InvalidHsv)?,
-                        saturation: words[1]
-                            .parse::<f32>()
-                            .map_err(|_| ColorParseError::InvalidHsv)?,
-                        value: words[2]
-                            .parse::<f32>()
-                            .map_err(|_| ColorParseError::InvalidHsv)?,
-                    })
-                } else {
-                    Err(ColorParseError::InvalidHsv)
+                // Graphviz separates HSV components with commas and/or whitespace
+                let words = s
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|word| !word.is_empty())
+                    .map(|word| word.parse::<f32>())
+                    .collect::<Result<Vec<f32>, _>>()
+                    .map_err(|_| ColorParseError::InvalidHsv)?;
+
+                match words[..] {
+                    [hue, saturation, value] => {
+                        if [hue, saturation, value]
+                            .iter()
+                            .all(|component| (0.0..=1.0).contains(component))
+                        {
+                            Ok(Self::Hsv {
+                                hue,
+                                saturation,
+                                value,
+                            })
+                        } else {
+                            Err(ColorParseError::HsvOutOfRange)
+                        }
+                    }
+                    _ => Err(ColorParseError::InvalidHsv),
                 }
             }
             None => Err(ColorParseError::Empty),
@@ -95,6 +385,130 @@ impl FromStr for Color {
     }
 }
 
+/// Whether `str` looks like a color name or a Brewer index, like `gray42` or `3`
+fn is_name(str: &str) -> bool {
+    str.chars().all(|c| c == '_' || c.is_alphanumeric())
+}
+
+/// Parse `rrggbb` or `rrggbbaa`, or their CSS shorthands `rgb` and `rgba`
+fn parse_hex(hex: &str) -> Result<Color, ColorParseError> {
+    let expanded;
+    let hex = match hex.len() {
+        3 | 4 => {
+            expanded = hex.chars().flat_map(|c| [c, c]).collect::<String>();
+            expanded.as_str()
+        }
+        _ => hex,
+    };
+
+    match hex.len() {
+        6 => {
+            let [red, green, blue] = <[u8; 3]>::from_hex(hex)?;
+            Ok(Color::rgb(red, green, blue))
+        }
+        8 => {
+            let [red, green, blue, alpha] = <[u8; 4]>::from_hex(hex)?;
+            Ok(Color::Rgba {
+                red,
+                green,
+                blue,
+                alpha,
+            })
+        }
+        _ => Err(ColorParseError::InvalidRgba(
+            FromHexError::InvalidStringLength,
+        )),
+    }
+}
+
+/// Parse the arguments to CSS's `rgb()`, `rgba()`, `hsl()` and `hsla()`, separated by commas,
+/// whitespace or a slash before the alpha
+fn parse_css(function: &str, arguments: &str) -> Option<Color> {
+    let arguments: Vec<&str> = arguments
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|argument| !argument.is_empty())
+        .collect();
+
+    let (components, alpha) = match arguments[..] {
+        [a, b, c] => ([a, b, c], 1.0),
+        [a, b, c, alpha] => ([a, b, c], css_fraction(alpha, 1.0)?),
+        _ => return None,
+    };
+
+    let color = match function.to_ascii_lowercase().as_str() {
+        "rgb" | "rgba" => {
+            let [red, green, blue] = components.map(|channel| css_fraction(channel, 255.0));
+            Color::rgb(to_byte(red?), to_byte(green?), to_byte(blue?))
+        }
+        "hsl" | "hsla" => {
+            let hue = components[0].strip_suffix("deg").unwrap_or(components[0]);
+            let hue = hue.parse::<f32>().ok()?.rem_euclid(360.0) / 360.0;
+            let saturation = components[1].strip_suffix('%')?.parse::<f32>().ok()? / 100.0;
+            let lightness = components[2].strip_suffix('%')?.parse::<f32>().ok()? / 100.0;
+            Color::hsl(hue, saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0))
+        }
+        _ => return None,
+    };
+
+    match color {
+        Color::Rgba {
+            red, green, blue, ..
+        } => Some(Color::Rgba {
+            red,
+            green,
+            blue,
+            alpha: to_byte(alpha),
+        }),
+        color => Some(color),
+    }
+}
+
+/// A CSS number or percentage as a fraction of `max`, clamped like browsers do
+fn css_fraction(value: &str, max: f32) -> Option<f32> {
+    let fraction = match value.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f32>().ok()? / 100.0,
+        None => value.parse::<f32>().ok()? / max,
+    };
+    Some(fraction.clamp(0.0, 1.0))
+}
+
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> [f32; 3] {
+    let hue = hue.rem_euclid(1.0) * 6.0;
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+
+    let [red, green, blue] = match hue as u8 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+
+    let min = value - chroma;
+    [red + min, green + min, blue + min]
+}
+
+/// The hue shared by HSV and HSL, from 0 to 1
+fn hue(red: f32, green: f32, blue: f32, max: f32, chroma: f32) -> f32 {
+    let hue = if chroma == 0.0 {
+        0.0
+    } else if max == red {
+        ((green - blue) / chroma).rem_euclid(6.0)
+    } else if max == green {
+        (blue - red) / chroma + 2.0
+    } else {
+        (red - green) / chroma + 4.0
+    };
+
+    hue / 6.0
+}
+
+fn to_byte(fraction: f32) -> u8 {
+    (fraction * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
 impl TryFrom<&str> for Color {
     type Error = ColorParseError;
 
@@ -103,6 +517,141 @@ impl TryFrom<&str> for Color {
     }
 }
 
+/// A list of colors, each optionally taking up a fraction of the whole
+///
+/// Graphviz uses lists to draw parallel multi-colored edges, to divide `wedged` and `striped`
+/// nodes, and as the two ends of a gradient fill. Colors without a fraction share whatever is
+/// left after the weighted ones, so the fractions can add up to at most 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorList {
+    colors: Vec<WeightedColor>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedColor {
+    pub color: Color,
+    pub fraction: Option<f32>,
+}
+
+impl ColorList {
+    pub fn new<I>(colors: I) -> Result<Self, ColorParseError>
+    where
+        I: IntoIterator,
+        I::Item: Into<WeightedColor>,
+    {
+        let colors: Vec<WeightedColor> = colors.into_iter().map(Into::into).collect();
+
+        if colors.is_empty() {
+            return Err(ColorParseError::Empty);
+        }
+
+        let mut total = 0.0;
+        for fraction in colors.iter().filter_map(|color| color.fraction) {
+            if !(0.0..=1.0).contains(&fraction) {
+                return Err(ColorParseError::InvalidFraction);
+            }
+            total += fraction;
+        }
+
+        if total > 1.0 {
+            return Err(ColorParseError::FractionsExceedOne(total));
+        }
+
+        Ok(Self { colors })
+    }
+
+    pub fn colors(&self) -> &[WeightedColor] {
+        &self.colors
+    }
+
+    pub fn as_string(&self) -> String {
+        let colors: Vec<String> = self
+            .colors
+            .iter()
+            .map(|weighted| match weighted.fraction {
+                Some(fraction) => format!("{};{fraction}", weighted.color.as_string()),
+                None => weighted.color.as_string(),
+            })
+            .collect();
+
+        colors.join(":")
+    }
+
+    /// Like `Color::from_id`, colors in the list that aren't RGBA or HSV are taken to be names
+    pub(crate) fn from_id(id: &Id) -> Option<Self> {
+        let colors = id.as_str().split(':').map(|item| {
+            let (color, fraction) = match item.split_once(';') {
+                Some((color, fraction)) => (color, Some(fraction.parse().ok()?)),
+                None => (item, None),
+            };
+
+            let color = color
+                .parse()
+                .unwrap_or_else(|_| Color::Name(color.to_string()));
+            Some(WeightedColor { color, fraction })
+        });
+
+        Self::new(colors.collect::<Option<Vec<_>>>()?).ok()
+    }
+}
+
+impl From<Color> for ColorList {
+    fn from(color: Color) -> Self {
+        Self {
+            colors: vec![color.into()],
+        }
+    }
+}
+
+impl FromStr for ColorList {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let colors = s
+            .split(':')
+            .map(|item| match item.split_once(';') {
+                Some((color, fraction)) => Ok(WeightedColor {
+                    color: color.parse()?,
+                    fraction: Some(
+                        fraction
+                            .parse()
+                            .map_err(|_| ColorParseError::InvalidFraction)?,
+                    ),
+                }),
+                None => Ok(WeightedColor::from(item.parse::<Color>()?)),
+            })
+            .collect::<Result<Vec<_>, ColorParseError>>()?;
+
+        Self::new(colors)
+    }
+}
+
+impl TryFrom<&str> for ColorList {
+    type Error = ColorParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse::<ColorList>()
+    }
+}
+
+impl From<Color> for WeightedColor {
+    fn from(color: Color) -> Self {
+        Self {
+            color,
+            fraction: None,
+        }
+    }
+}
+
+impl From<(Color, f32)> for WeightedColor {
+    fn from((color, fraction): (Color, f32)) -> Self {
+        Self {
+            color,
+            fraction: Some(fraction),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,4 +711,231 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn color_list() {
+        let red = Color::Name("red".into());
+        let blue = Color::Name("blue".into());
+
+        let list = ColorList::new([(red.clone(), 0.3)]).unwrap();
+        assert_eq!(list.as_string(), "red;0.3");
+
+        let list = ColorList::new([WeightedColor::from((red, 0.3)), blue.into()]).unwrap();
+        assert_eq!(list.as_string(), "red;0.3:blue");
+
+        assert_eq!(
+            "#ff0000;0.25:#0000ff"
+                .parse::<ColorList>()
+                .map(|list| list.as_string()),
+            Ok("#ff0000ff;0.25:#0000ffff".to_string())
+        );
+    }
+
+    #[test]
+    fn color_list_validation() {
+        assert_eq!(
+            ColorList::new(Vec::<WeightedColor>::new()),
+            Err(ColorParseError::Empty)
+        );
+        assert_eq!(
+            "#ff0000;0.75:#0000ff;0.5".parse::<ColorList>(),
+            Err(ColorParseError::FractionsExceedOne(1.25))
+        );
+        assert_eq!(
+            "#ff0000;-0.5".parse::<ColorList>(),
+            Err(ColorParseError::InvalidFraction)
+        );
+        assert_eq!(
+            "#ff0000;half".parse::<ColorList>(),
+            Err(ColorParseError::InvalidFraction)
+        );
+    }
+
+    #[test]
+    fn names_and_schemes() {
+        assert_eq!("Red".parse::<Color>(), Ok(Color::Name("Red".into())));
+        assert_eq!("aqua".parse::<Color>(), Ok(Color::Name("aqua".into())));
+        assert_eq!("3".parse::<Color>(), Ok(Color::Name("3".into())));
+        assert_eq!(
+            "blu-ish".parse::<Color>(),
+            Err(ColorParseError::UnknownName("blu-ish".into()))
+        );
+
+        let color = "/blues9/3".parse::<Color>().unwrap();
+        assert_eq!(
+            color,
+            Color::Scheme {
+                scheme: ColorScheme::brewer("blues", 9).unwrap(),
+                index_or_name: "3".into()
+            }
+        );
+        assert_eq!(color.as_string(), "/blues9/3");
+
+        assert_eq!(
+            Color::scheme(ColorScheme::Svg, "gray42"),
+            Err(ColorParseError::NotInScheme("svg".into(), "gray42".into()))
+        );
+        assert_eq!(
+            "/blues12/3".parse::<Color>(),
+            Err(ColorParseError::UnknownScheme("blues12".into()))
+        );
+    }
+
+    #[test]
+    fn css() {
+        assert_eq!("#0f8".parse(), Ok(Color::rgb(0x00, 0xff, 0x88)));
+        assert_eq!(
+            "#0f88".parse(),
+            Ok(Color::Rgba {
+                red: 0x00,
+                green: 0xff,
+                blue: 0x88,
+                alpha: 0x88
+            })
+        );
+        assert_eq!("rgb(255, 0, 128)".parse(), Ok(Color::rgb(255, 0, 128)));
+        assert_eq!("rgb(100% 0% 50%)".parse(), Ok(Color::rgb(255, 0, 128)));
+        assert_eq!(
+            "rgba(255, 0, 128, 0.5)".parse(),
+            Ok(Color::Rgba {
+                red: 255,
+                green: 0,
+                blue: 128,
+                alpha: 128
+            })
+        );
+        assert_eq!("hsl(120, 100%, 50%)".parse(), Ok(Color::rgb(0, 255, 0)));
+        assert_eq!("hsl(240deg 100% 25%)".parse(), Ok(Color::rgb(0, 0, 128)));
+
+        assert_eq!(
+            "rgb(1, 2)".parse::<Color>(),
+            Err(ColorParseError::InvalidCss("rgb(1, 2)".into()))
+        );
+        assert_eq!(
+            "cmyk(0, 0, 0, 0)".parse::<Color>(),
+            Err(ColorParseError::InvalidCss("cmyk(0, 0, 0, 0)".into()))
+        );
+    }
+
+    #[test]
+    fn hsv_validation() {
+        assert_eq!(
+            "0.5,0.25,0.8".parse(),
+            Ok(Color::Hsv {
+                hue: 0.5,
+                saturation: 0.25,
+                value: 0.8
+            })
+        );
+        assert_eq!(
+            "5 -3 900".parse::<Color>(),
+            Err(ColorParseError::HsvOutOfRange)
+        );
+        assert_eq!(
+            "0.5 0.25".parse::<Color>(),
+            Err(ColorParseError::InvalidHsv)
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        let orange = Color::rgb(255, 128, 0);
+        let [hue, saturation, value] = orange.to_hsv().unwrap();
+        assert!((hue - 30.0 / 360.0).abs() < 0.005);
+        assert_eq!((saturation, value), (1.0, 1.0));
+
+        let [hue, saturation, lightness] = orange.to_hsl().unwrap();
+        assert!((hue - 30.0 / 360.0).abs() < 0.005);
+        assert_eq!(saturation, 1.0);
+        assert!((lightness - 0.5).abs() < 0.005);
+
+        let hsv = Color::Hsv {
+            hue,
+            saturation: 1.0,
+            value: 1.0,
+        };
+        assert_eq!(hsv.to_rgba(), Some([255, 128, 0, 255]));
+        assert_eq!(Color::hsl(hue, 1.0, lightness).to_rgba(), orange.to_rgba());
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(
+            Color::Name("SteelBlue".into()).to_rgba(),
+            Some([70, 130, 180, 255])
+        );
+        assert_eq!(
+            Color::Name("green".into()).to_rgba(),
+            Some([0, 255, 0, 255])
+        );
+        assert_eq!(
+            Color::scheme(ColorScheme::Svg, "green").unwrap().to_rgba(),
+            Some([0, 128, 0, 255])
+        );
+        assert_eq!(Color::Name("3".into()).to_rgba(), None);
+
+        for name in ["transparent", "None", "INVIS"] {
+            assert_eq!(name.parse(), Ok(Color::Name(name.into())));
+            assert_eq!(
+                Color::Name(name.into()).to_rgba(),
+                Some([0xFF, 0xFF, 0xFE, 0x00])
+            );
+        }
+        assert_eq!(
+            Color::scheme(ColorScheme::X11, "none").unwrap().to_rgba(),
+            Some([0xFF, 0xFF, 0xFE, 0x00])
+        );
+    }
+
+    #[test]
+    fn manipulation() {
+        let red = Color::rgb(255, 0, 0);
+        assert_eq!(red.lighten(0.25), Some(Color::rgb(255, 128, 128)));
+        assert_eq!(red.darken(0.25), Some(Color::rgb(128, 0, 0)));
+        assert_eq!(red.darken(2.0), Some(Color::rgb(0, 0, 0)));
+        assert_eq!(red.desaturate(1.0), Some(Color::rgb(128, 128, 128)));
+        let gray = Color::rgb(128, 128, 128);
+        let saturation = gray.saturate(0.5).unwrap().to_hsl().unwrap()[1];
+        assert!((saturation - 0.5).abs() < 0.01);
+
+        let blue = Color::Name("blue".into());
+        assert_eq!(red.mix(&blue, 0.5), Some(Color::rgb(128, 0, 128)));
+        assert_eq!(red.mix(&blue, 0.0), Some(red.clone()));
+        assert_eq!(
+            blue.with_alpha(0.5),
+            Some(Color::Rgba {
+                red: 0,
+                green: 0,
+                blue: 255,
+                alpha: 128
+            })
+        );
+
+        let translucent = red.with_alpha(0.5).unwrap();
+        assert_eq!(
+            translucent.lighten(0.25).unwrap().to_rgba().unwrap()[3],
+            128
+        );
+
+        assert_eq!(Color::Name("3".into()).lighten(0.1), None);
+    }
+
+    #[test]
+    fn contrast() {
+        let black = Color::Name("black".into());
+        let white = Color::Name("white".into());
+        assert_eq!(black.relative_luminance(), Some(0.0));
+        assert!((white.relative_luminance().unwrap() - 1.0).abs() < 0.001);
+        assert!((black.contrast_ratio(&white).unwrap() - 21.0).abs() < 0.001);
+        assert_eq!(white.contrast_ratio(&white), Some(1.0));
+
+        assert_eq!(
+            Color::Name("navy".into()).readable_text_color(),
+            Some(Color::rgb(255, 255, 255))
+        );
+        assert_eq!(
+            Color::Name("yellow".into()).readable_text_color(),
+            Some(Color::rgb(0, 0, 0))
+        );
+    }
 }
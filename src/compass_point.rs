@@ -1,3 +1,7 @@
+use crate::UnknownNameError;
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CompassPoint {
     North,
     NorthEast,
@@ -12,6 +16,22 @@ pub enum CompassPoint {
 }
 
 impl CompassPoint {
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "n" => Some(CompassPoint::North),
+            "ne" => Some(CompassPoint::NorthEast),
+            "e" => Some(CompassPoint::East),
+            "se" => Some(CompassPoint::SouthEast),
+            "s" => Some(CompassPoint::South),
+            "sw" => Some(CompassPoint::SouthWest),
+            "w" => Some(CompassPoint::West),
+            "nw" => Some(CompassPoint::NorthWest),
+            "c" => Some(CompassPoint::Center),
+            "_" => Some(CompassPoint::Adjacent),
+            _ => None,
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             CompassPoint::North => "n",
@@ -27,3 +47,17 @@ impl CompassPoint {
         }
     }
 }
+
+impl FromStr for CompassPoint {
+    type Err = UnknownNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s).ok_or_else(|| UnknownNameError::new("compass point", s))
+    }
+}
+
+impl fmt::Display for CompassPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -1,40 +1,89 @@
 use crate::{
-    utils::{sanitize, write_attributes, Attribute},
-    Color, CompassPoint, Label,
+    attributes::{Attribute, AttributeValue, Attributes},
+    graph::GraphType,
+    lexer::Id,
+    parser::parse_bool,
+    utils::{
+        add_styles, bool_string, parse_line_width, parse_styles, sanitize, styles_string,
+        write_attributes,
+    },
+    ArrowShape, Color, ColorList, ColorParseError, ColorScheme, Graph, Label, NodeRef, Point, Port,
+    Unit, UnknownNameError,
+};
+use std::{
+    fmt::{self, Debug},
+    io,
+    str::FromStr,
 };
-use std::{fmt::Debug, io};
 
+/// An edge statement, connecting two or more endpoints in a chain
+#[derive(Debug, Clone, PartialEq)]
 pub struct Edge {
-    pub from: String,
-    pub to: String,
-    pub attributes: Vec<EdgeAttribute>,
+    pub endpoints: Vec<Endpoint>,
+    pub attributes: Attributes<EdgeAttribute>,
 }
 
 impl Edge {
-    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+    pub fn new(from: impl Into<Endpoint>, to: impl Into<Endpoint>) -> Self {
+        Self {
+            endpoints: vec![from.into(), to.into()],
+            attributes: Attributes::new(),
+        }
+    }
+
+    /// An edge through all of the `endpoints`, like `a -> b -> c`
+    pub fn chain<E>(endpoints: impl IntoIterator<Item = E>) -> Self
+    where
+        E: Into<Endpoint>,
+    {
         Self {
-            from: from.into(),
-            to: to.into(),
-            attributes: Vec::new(),
+            endpoints: endpoints.into_iter().map(Into::into).collect(),
+            attributes: Attributes::new(),
         }
     }
 
+    /// Extend the chain with another endpoint
+    pub fn then(mut self, endpoint: impl Into<Endpoint>) -> Self {
+        self.endpoints.push(endpoint.into());
+        self
+    }
+
     // --- Attributes --- //
 
+    /// Panics if `color` can't be converted, use `try_color` for colors from external input
     pub fn color<C>(self, color: C) -> Self
     where
-        C: TryInto<Color>,
+        C: TryInto<ColorList>,
         C::Error: Debug,
     {
         let color = color.try_into().unwrap();
         self.attribute(EdgeAttribute::Color(color))
     }
 
+    pub fn try_color<C>(self, color: C) -> Result<Self, ColorParseError>
+    where
+        C: TryInto<ColorList>,
+        C::Error: Into<ColorParseError>,
+    {
+        let color = color.try_into().map_err(Into::into)?;
+        Ok(self.attribute(EdgeAttribute::Color(color)))
+    }
+
+    pub fn color_scheme(self, scheme: ColorScheme) -> Self {
+        self.attribute(EdgeAttribute::ColorScheme(scheme))
+    }
+
+    /// The head label position, in points
+    pub fn head_lp(self, head_lp: Point) -> Self {
+        self.attribute(EdgeAttribute::HeadLp(head_lp))
+    }
+
     pub fn head_label(self, label: impl Into<Label>) -> Self {
         self.attribute(EdgeAttribute::HeadLabel(label.into()))
     }
 
-    pub fn head_port(self, port: impl Into<CompassPoint>) -> Self {
+    /// Where the edge attaches to its head node, as a compass point, a port name or both
+    pub fn head_port(self, port: impl Into<Port>) -> Self {
         self.attribute(EdgeAttribute::HeadPort(port.into()))
     }
 
@@ -42,6 +91,11 @@ impl Edge {
         self.attribute(EdgeAttribute::Label(label.into()))
     }
 
+    /// The label position, in points
+    pub fn lp(self, lp: Point) -> Self {
+        self.attribute(EdgeAttribute::Lp(lp))
+    }
+
     pub fn lhead(self, lhead: impl Into<String>) -> Self {
         self.attribute(EdgeAttribute::LHead(lhead.into()))
     }
@@ -50,15 +104,34 @@ impl Edge {
         self.attribute(EdgeAttribute::LTail(ltail.into()))
     }
 
+    /// Add a style, combining it with any styles set before
     pub fn style(self, style: EdgeStyle) -> Self {
-        self.attribute(EdgeAttribute::Style(style))
+        self.styles([style])
+    }
+
+    pub fn styles(mut self, styles: impl IntoIterator<Item = EdgeStyle>) -> Self {
+        match self.attributes.get_mut("style") {
+            Some(EdgeAttribute::Style(existing)) => add_styles(existing, styles),
+            _ => {
+                let mut new = Vec::new();
+                add_styles(&mut new, styles);
+                self.attributes.set(EdgeAttribute::Style(new));
+            }
+        }
+        self
+    }
+
+    /// The tail label position, in points
+    pub fn tail_lp(self, tail_lp: Point) -> Self {
+        self.attribute(EdgeAttribute::TailLp(tail_lp))
     }
 
     pub fn tail_label(self, label: impl Into<Label>) -> Self {
         self.attribute(EdgeAttribute::TailLabel(label.into()))
     }
 
-    pub fn tail_port(self, port: impl Into<CompassPoint>) -> Self {
+    /// Where the edge attaches to its tail node, as a compass point, a port name or both
+    pub fn tail_port(self, port: impl Into<Port>) -> Self {
         self.attribute(EdgeAttribute::TailPort(port.into()))
     }
 
@@ -74,16 +147,221 @@ impl Edge {
         self.attribute(EdgeAttribute::PenWidth(width))
     }
 
+    pub fn arrow_head(self, shape: impl Into<ArrowShape>) -> Self {
+        self.attribute(EdgeAttribute::ArrowHead(shape.into()))
+    }
+
+    pub fn arrow_size(self, size: f32) -> Self {
+        self.attribute(EdgeAttribute::ArrowSize(size))
+    }
+
+    pub fn arrow_tail(self, shape: impl Into<ArrowShape>) -> Self {
+        self.attribute(EdgeAttribute::ArrowTail(shape.into()))
+    }
+
+    pub fn constraint(self, constraint: bool) -> Self {
+        self.attribute(EdgeAttribute::Constraint(constraint))
+    }
+
+    pub fn decorate(self, decorate: bool) -> Self {
+        self.attribute(EdgeAttribute::Decorate(decorate))
+    }
+
+    pub fn dir(self, dir: Direction) -> Self {
+        self.attribute(EdgeAttribute::Dir(dir))
+    }
+
+    pub fn edge_url(self, url: impl Into<String>) -> Self {
+        self.attribute(EdgeAttribute::EdgeUrl(url.into()))
+    }
+
+    /// Panics if `color` can't be converted, use `try_font_color` for colors from external input
+    pub fn font_color<C>(self, color: C) -> Self
+    where
+        C: TryInto<Color>,
+        C::Error: Debug,
+    {
+        let color = color.try_into().unwrap();
+        self.attribute(EdgeAttribute::FontColor(color))
+    }
+
+    pub fn try_font_color<C>(self, color: C) -> Result<Self, ColorParseError>
+    where
+        C: TryInto<Color>,
+        C::Error: Into<ColorParseError>,
+    {
+        let color = color.try_into().map_err(Into::into)?;
+        Ok(self.attribute(EdgeAttribute::FontColor(color)))
+    }
+
+    pub fn font_name(self, font_name: impl Into<String>) -> Self {
+        self.attribute(EdgeAttribute::FontName(font_name.into()))
+    }
+
+    pub fn font_size(self, font_size: f32) -> Self {
+        self.attribute(EdgeAttribute::FontSize(font_size))
+    }
+
+    pub fn head_clip(self, clip: bool) -> Self {
+        self.attribute(EdgeAttribute::HeadClip(clip))
+    }
+
+    pub fn head_url(self, url: impl Into<String>) -> Self {
+        self.attribute(EdgeAttribute::HeadUrl(url.into()))
+    }
+
+    pub fn label_angle(self, degrees: f32) -> Self {
+        self.attribute(EdgeAttribute::LabelAngle(degrees))
+    }
+
+    pub fn label_distance(self, distance: f32) -> Self {
+        self.attribute(EdgeAttribute::LabelDistance(distance))
+    }
+
+    pub fn label_float(self, float: bool) -> Self {
+        self.attribute(EdgeAttribute::LabelFloat(float))
+    }
+
+    pub fn min_len(self, min_len: u32) -> Self {
+        self.attribute(EdgeAttribute::MinLen(min_len))
+    }
+
+    /// The `id` attribute, used to identify the edge in SVG and image map output
+    pub fn svg_id(self, id: impl Into<String>) -> Self {
+        self.attribute(EdgeAttribute::Id(id.into()))
+    }
+
+    pub fn tail_clip(self, clip: bool) -> Self {
+        self.attribute(EdgeAttribute::TailClip(clip))
+    }
+
+    pub fn tail_url(self, url: impl Into<String>) -> Self {
+        self.attribute(EdgeAttribute::TailUrl(url.into()))
+    }
+
+    pub fn tooltip(self, tooltip: impl Into<String>) -> Self {
+        self.attribute(EdgeAttribute::Tooltip(tooltip.into()))
+    }
+
+    pub fn url(self, url: impl Into<String>) -> Self {
+        self.attribute(EdgeAttribute::Url(url.into()))
+    }
+
+    pub fn weight(self, weight: f32) -> Self {
+        self.attribute(EdgeAttribute::Weight(weight))
+    }
+
+    pub fn xlabel(self, label: impl Into<Label>) -> Self {
+        self.attribute(EdgeAttribute::XLabel(label.into()))
+    }
+
+    /// The external label position, in points
+    pub fn xlp(self, xlp: Point) -> Self {
+        self.attribute(EdgeAttribute::XLp(xlp))
+    }
+
+    /// Set an attribute, replacing any earlier attribute with the same key
     pub fn attribute(mut self, attribute: EdgeAttribute) -> Self {
-        self.attributes.push(attribute);
+        self.attributes.set(attribute);
         self
     }
 
-    pub(crate) fn write(&self, directed: bool, mut w: impl io::Write) -> io::Result<()> {
-        if directed {
-            write!(w, "{} -> {}", sanitize(&self.from), sanitize(&self.to))?;
+    /// Set an attribute of a user-defined type, see `Attribute` for how to format its value
+    pub fn attr(self, attribute: impl Attribute) -> Self {
+        let (key, value) = attribute.pair();
+        let attribute = EdgeAttribute::Unknown(key.to_string(), AttributeValue::Raw(value));
+        self.attribute(attribute)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&EdgeAttribute> {
+        self.attributes.get(key)
+    }
+
+    /// Set an attribute after construction, returning the one it replaced, if any
+    pub fn set(&mut self, attribute: EdgeAttribute) -> Option<EdgeAttribute> {
+        self.attributes.set(attribute)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<EdgeAttribute> {
+        self.attributes.remove(key)
+    }
+
+    /// Identifies the pair of nodes this edge connects, if it is a single node-to-node edge
+    pub(crate) fn key(&self, directed: bool) -> Option<(String, String)> {
+        let (from, to) = match self.endpoints.as_slice() {
+            [Endpoint::Node(from), Endpoint::Node(to)] => (from.id.clone(), to.id.clone()),
+            _ => return None,
+        };
+
+        if directed || from <= to {
+            Some((from, to))
         } else {
-            write!(w, "{} -- {}", sanitize(&self.from), sanitize(&self.to))?;
+            Some((to, from))
+        }
+    }
+
+    /// Merge a later duplicate of this edge into it, like a strict graph does
+    ///
+    /// Its attributes replace those of this edge, and so do the ports it sets, as they are the
+    /// `tailport` and `headport` attributes of the merged edge.
+    pub(crate) fn merge(&mut self, later: &Edge, directed: bool) {
+        self.attributes.extend(later.attributes.iter().cloned());
+
+        if let (
+            [Endpoint::Node(from), Endpoint::Node(to)],
+            [Endpoint::Node(later_from), Endpoint::Node(later_to)],
+        ) = (self.endpoints.as_mut_slice(), later.endpoints.as_slice())
+        {
+            let (from, to) = if directed || from.id == later_from.id {
+                (from, to)
+            } else {
+                (to, from)
+            };
+
+            for (node, later_node) in [(from, later_from), (to, later_to)] {
+                if later_node.port.is_some() || later_node.compass.is_some() {
+                    node.port = later_node.port.clone();
+                    node.compass = later_node.compass.clone();
+                }
+            }
+        }
+    }
+
+    /// The keys of the node pairs along the chain, with `None` for pairs involving a subgraph
+    pub(crate) fn chain_keys(&self, directed: bool) -> Vec<Option<(String, String)>> {
+        self.split().iter().map(|edge| edge.key(directed)).collect()
+    }
+
+    /// Split a chain into one edge per pair of consecutive endpoints, each with all attributes
+    pub(crate) fn split(&self) -> Vec<Edge> {
+        self.endpoints
+            .windows(2)
+            .map(|pair| Edge {
+                endpoints: pair.to_vec(),
+                attributes: self.attributes.clone(),
+            })
+            .collect()
+    }
+
+    pub(crate) fn write(&self, directed: bool, mut w: impl io::Write) -> io::Result<()> {
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            if index > 0 {
+                write!(w, " {} ", if directed { "->" } else { "--" })?;
+            }
+
+            match endpoint {
+                Endpoint::Node(node) => node.write(&mut w)?,
+                Endpoint::Subgraph(subgraph) if subgraph.is_inline() => {
+                    subgraph.write_inline(&mut w)?
+                }
+                Endpoint::Subgraph(subgraph) => subgraph.write(
+                    directed,
+                    GraphType::Subgraph {
+                        cluster: subgraph.cluster,
+                    },
+                    &mut w,
+                )?,
+            }
         }
 
         if !self.attributes.is_empty() {
@@ -94,42 +372,260 @@ impl Edge {
     }
 }
 
+/// One end of an edge: a node, or every node in a subgraph
+#[derive(Debug, Clone, PartialEq)]
+pub enum Endpoint {
+    Node(NodeRef),
+    Subgraph(Graph),
+}
+
+impl From<NodeRef> for Endpoint {
+    fn from(node: NodeRef) -> Self {
+        Self::Node(node)
+    }
+}
+
+impl From<&str> for Endpoint {
+    fn from(id: &str) -> Self {
+        Self::Node(id.into())
+    }
+}
+
+impl From<String> for Endpoint {
+    fn from(id: String) -> Self {
+        Self::Node(id.into())
+    }
+}
+
+impl From<Graph> for Endpoint {
+    fn from(subgraph: Graph) -> Self {
+        Self::Subgraph(subgraph)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum EdgeAttribute {
-    Color(Color),
+    ArrowHead(ArrowShape),
+    ArrowSize(f32),
+    ArrowTail(ArrowShape),
+    Color(ColorList),
+    ColorScheme(ColorScheme),
+    Constraint(bool),
+    Decorate(bool),
+    Dir(Direction),
+    EdgeUrl(String),
+    FontColor(Color),
+    FontName(String),
+    FontSize(f32),
+    HeadClip(bool),
     HeadLabel(Label),
-    HeadPort(CompassPoint),
+    HeadLp(Point),
+    HeadPort(Port),
+    HeadUrl(String),
+    Id(String),
     Label(Label),
+    LabelAngle(f32),
+    LabelDistance(f32),
+    LabelFloat(bool),
+    Lp(Point),
     LHead(String),
     LTail(String),
+    MinLen(u32),
     PenWidth(f32),
     SameHead(String),
     SameTail(String),
-    Style(EdgeStyle),
+    Style(Vec<EdgeStyle>),
+    TailClip(bool),
     TailLabel(Label),
-    TailPort(CompassPoint),
-    Unknown(String, String),
+    TailLp(Point),
+    TailPort(Port),
+    TailUrl(String),
+    Tooltip(String),
+    Url(String),
+    Weight(f32),
+    XLabel(Label),
+    XLp(Point),
+    Unknown(String, AttributeValue),
+}
+
+impl EdgeAttribute {
+    pub(crate) fn from_pair(key: &str, value: Id) -> Self {
+        let str = value.as_str();
+
+        let attribute = match key {
+            "arrowhead" => str.parse().ok().map(Self::ArrowHead),
+            "arrowsize" => str.parse().ok().map(Self::ArrowSize),
+            "arrowtail" => str.parse().ok().map(Self::ArrowTail),
+            "color" => ColorList::from_id(&value).map(Self::Color),
+            "colorscheme" => str.parse().ok().map(Self::ColorScheme),
+            "constraint" => parse_bool(str).map(Self::Constraint),
+            "decorate" => parse_bool(str).map(Self::Decorate),
+            "dir" => Direction::from_name(str).map(Self::Dir),
+            "edgeURL" | "edgehref" => Some(Self::EdgeUrl(value.unescaped())),
+            "fontcolor" => return Self::FontColor(Color::from_id(value)),
+            "fontname" => Some(Self::FontName(value.unescaped())),
+            "fontsize" => str.parse().ok().map(Self::FontSize),
+            "headclip" => parse_bool(str).map(Self::HeadClip),
+            "headlabel" => return Self::HeadLabel(Label::from_id(value)),
+            "head_lp" => Point::parse(str).map(Self::HeadLp),
+            "headport" => Some(Self::HeadPort(Port::parse(&value.unescaped()))),
+            "headURL" | "headhref" => Some(Self::HeadUrl(value.unescaped())),
+            "id" => Some(Self::Id(value.unescaped())),
+            "label" => return Self::Label(Label::from_id(value)),
+            "labelangle" => str.parse().ok().map(Self::LabelAngle),
+            "labeldistance" => str.parse().ok().map(Self::LabelDistance),
+            "labelfloat" => parse_bool(str).map(Self::LabelFloat),
+            "lp" => Point::parse(str).map(Self::Lp),
+            "lhead" => Some(Self::LHead(value.unescaped())),
+            "ltail" => Some(Self::LTail(value.unescaped())),
+            "minlen" => str.parse().ok().map(Self::MinLen),
+            "penwidth" => str.parse().ok().map(Self::PenWidth),
+            "samehead" => Some(Self::SameHead(value.unescaped())),
+            "sametail" => Some(Self::SameTail(value.unescaped())),
+            "style" => parse_styles(str, EdgeStyle::from_name).map(Self::Style),
+            "tailclip" => parse_bool(str).map(Self::TailClip),
+            "taillabel" => return Self::TailLabel(Label::from_id(value)),
+            "tail_lp" => Point::parse(str).map(Self::TailLp),
+            "tailport" => Some(Self::TailPort(Port::parse(&value.unescaped()))),
+            "tailURL" | "tailhref" => Some(Self::TailUrl(value.unescaped())),
+            "tooltip" => Some(Self::Tooltip(value.unescaped())),
+            "URL" | "href" => Some(Self::Url(value.unescaped())),
+            "weight" => str.parse().ok().map(Self::Weight),
+            "xlabel" => return Self::XLabel(Label::from_id(value)),
+            "xlp" => Point::parse(str).map(Self::XLp),
+            _ => None,
+        };
+
+        attribute.unwrap_or_else(|| Self::Unknown(key.to_string(), AttributeValue::from_id(value)))
+    }
 }
 
 impl Attribute for EdgeAttribute {
     fn pair(&self) -> (&str, String) {
         match self {
+            Self::ArrowHead(shape) => ("arrowhead", shape.as_string()),
+            Self::ArrowSize(size) => ("arrowsize", format!("{size}")),
+            Self::ArrowTail(shape) => ("arrowtail", shape.as_string()),
             Self::Color(color) => ("color", sanitize(&color.as_string())),
+            Self::ColorScheme(scheme) => ("colorscheme", scheme.as_string()),
+            Self::Constraint(constraint) => ("constraint", bool_string(*constraint)),
+            Self::Decorate(decorate) => ("decorate", bool_string(*decorate)),
+            Self::Dir(dir) => ("dir", dir.as_str().to_string()),
+            Self::EdgeUrl(url) => ("edgeURL", sanitize(url)),
+            Self::FontColor(color) => ("fontcolor", sanitize(&color.as_string())),
+            Self::FontName(font_name) => ("fontname", sanitize(font_name)),
+            Self::FontSize(font_size) => ("fontsize", format!("{font_size}")),
+            Self::HeadClip(clip) => ("headclip", bool_string(*clip)),
             Self::HeadLabel(label) => ("headlabel", label.as_string()),
-            Self::HeadPort(compass_point) => ("headport", compass_point.as_str().to_string()),
+            Self::HeadLp(lp) => ("head_lp", sanitize(&lp.as_string_in(Unit::Points))),
+            Self::HeadPort(port) => ("headport", sanitize(&port.as_string())),
+            Self::HeadUrl(url) => ("headURL", sanitize(url)),
+            Self::Id(id) => ("id", sanitize(id)),
             Self::Label(label) => ("label", label.as_string()),
+            Self::LabelAngle(degrees) => ("labelangle", format!("{degrees}")),
+            Self::LabelDistance(distance) => ("labeldistance", format!("{distance}")),
+            Self::LabelFloat(float) => ("labelfloat", bool_string(*float)),
+            Self::Lp(lp) => ("lp", sanitize(&lp.as_string_in(Unit::Points))),
             Self::LHead(head) => ("lhead", sanitize(head)),
             Self::LTail(tail) => ("ltail", sanitize(tail)),
+            Self::MinLen(min_len) => ("minlen", format!("{min_len}")),
             Self::PenWidth(width) => ("penwidth", format!("{width}")),
-            Self::Style(style) => ("style", style.as_str().to_string()),
+            Self::Style(styles) => ("style", styles_string(styles, EdgeStyle::as_string)),
             Self::SameHead(samehead) => ("samehead", sanitize(samehead)),
             Self::SameTail(sametail) => ("sametail", sanitize(sametail)),
+            Self::TailClip(clip) => ("tailclip", bool_string(*clip)),
             Self::TailLabel(label) => ("taillabel", label.as_string()),
-            Self::TailPort(compass_point) => ("tailport", compass_point.as_str().to_string()),
-            Self::Unknown(key, value) => (key, sanitize(value)),
+            Self::TailLp(lp) => ("tail_lp", sanitize(&lp.as_string_in(Unit::Points))),
+            Self::TailPort(port) => ("tailport", sanitize(&port.as_string())),
+            Self::TailUrl(url) => ("tailURL", sanitize(url)),
+            Self::Tooltip(tooltip) => ("tooltip", sanitize(tooltip)),
+            Self::Url(url) => ("URL", sanitize(url)),
+            Self::Weight(weight) => ("weight", format!("{weight}")),
+            Self::XLabel(label) => ("xlabel", label.as_string()),
+            Self::XLp(xlp) => ("xlp", sanitize(&xlp.as_string_in(Unit::Points))),
+            Self::Unknown(key, value) => (key, value.as_string()),
+        }
+    }
+
+    fn key(&self) -> &str {
+        match self {
+            Self::ArrowHead(_) => "arrowhead",
+            Self::ArrowSize(_) => "arrowsize",
+            Self::ArrowTail(_) => "arrowtail",
+            Self::Color(_) => "color",
+            Self::ColorScheme(_) => "colorscheme",
+            Self::Constraint(_) => "constraint",
+            Self::Decorate(_) => "decorate",
+            Self::Dir(_) => "dir",
+            Self::EdgeUrl(_) => "edgeURL",
+            Self::FontColor(_) => "fontcolor",
+            Self::FontName(_) => "fontname",
+            Self::FontSize(_) => "fontsize",
+            Self::HeadClip(_) => "headclip",
+            Self::HeadLabel(_) => "headlabel",
+            Self::HeadLp(_) => "head_lp",
+            Self::HeadPort(_) => "headport",
+            Self::HeadUrl(_) => "headURL",
+            Self::Id(_) => "id",
+            Self::Label(_) => "label",
+            Self::LabelAngle(_) => "labelangle",
+            Self::LabelDistance(_) => "labeldistance",
+            Self::LabelFloat(_) => "labelfloat",
+            Self::Lp(_) => "lp",
+            Self::LHead(_) => "lhead",
+            Self::LTail(_) => "ltail",
+            Self::MinLen(_) => "minlen",
+            Self::PenWidth(_) => "penwidth",
+            Self::Style(_) => "style",
+            Self::SameHead(_) => "samehead",
+            Self::SameTail(_) => "sametail",
+            Self::TailClip(_) => "tailclip",
+            Self::TailLabel(_) => "taillabel",
+            Self::TailLp(_) => "tail_lp",
+            Self::TailPort(_) => "tailport",
+            Self::TailUrl(_) => "tailURL",
+            Self::Tooltip(_) => "tooltip",
+            Self::Url(_) => "URL",
+            Self::Weight(_) => "weight",
+            Self::XLabel(_) => "xlabel",
+            Self::XLp(_) => "xlp",
+            Self::Unknown(key, _) => key,
+        }
+    }
+}
+
+/// Which ends of an edge get an arrowhead
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Forward,
+    Back,
+    Both,
+    None,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Forward => "forward",
+            Self::Back => "back",
+            Self::Both => "both",
+            Self::None => "none",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "forward" => Some(Self::Forward),
+            "back" => Some(Self::Back),
+            "both" => Some(Self::Both),
+            "none" => Some(Self::None),
+            _ => None,
         }
     }
 }
 
+/// An edge style, several of which can be combined through `Edge::styles`
+#[derive(Debug, Clone, PartialEq)]
 pub enum EdgeStyle {
     Bold,
     Dashed,
@@ -137,10 +633,38 @@ pub enum EdgeStyle {
     Invisible,
     Solid,
     Tapered,
+
+    /// Deprecated by Graphviz in favor of `penwidth`, but still understood
+    SetLineWidth(f32),
 }
 
 impl EdgeStyle {
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "bold" => Some(Self::Bold),
+            "dashed" => Some(Self::Dashed),
+            "dotted" => Some(Self::Dotted),
+            "invis" => Some(Self::Invisible),
+            "solid" => Some(Self::Solid),
+            "tapered" => Some(Self::Tapered),
+            _ => parse_line_width(name).map(Self::SetLineWidth),
+        }
+    }
+
+    /// The style's name, without the width of `setlinewidth`
+    #[deprecated(note = "use `as_string`, which includes the width of `setlinewidth`")]
     pub fn as_str(&self) -> &'static str {
+        self.name()
+    }
+
+    pub fn as_string(&self) -> String {
+        match self {
+            Self::SetLineWidth(width) => format!("{}({width})", self.name()),
+            style => style.name().to_string(),
+        }
+    }
+
+    fn name(&self) -> &'static str {
         match self {
             Self::Dashed => "dashed",
             Self::Dotted => "dotted",
@@ -148,6 +672,21 @@ impl EdgeStyle {
             Self::Invisible => "invis",
             Self::Bold => "bold",
             Self::Tapered => "tapered",
+            Self::SetLineWidth(_) => "setlinewidth",
         }
     }
 }
+
+impl FromStr for EdgeStyle {
+    type Err = UnknownNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s).ok_or_else(|| UnknownNameError::new("edge style", s))
+    }
+}
+
+impl fmt::Display for EdgeStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.as_string())
+    }
+}
@@ -0,0 +1,408 @@
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The unit a coordinate is measured in
+///
+/// Graphviz expects each attribute in a fixed unit: positions and bounding boxes in points,
+/// margins and sizes in inches. Geometry without a unit is written as-is in whatever unit the
+/// attribute expects, and geometry with one is converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Inches,
+    Points,
+}
+
+impl Unit {
+    /// How many of this unit fit in an inch
+    fn per_inch(&self) -> f32 {
+        match self {
+            Self::Inches => 1.0,
+            Self::Points => 72.0,
+        }
+    }
+
+    fn convert(value: f32, from: Option<Unit>, to: Unit) -> f32 {
+        match from {
+            Some(from) => value / from.per_inch() * to.per_inch(),
+            None => value,
+        }
+    }
+}
+
+/// A position or offset, written as `"x,y"`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+    pub unit: Option<Unit>,
+
+    /// Keep the node at this position, written as a trailing `!`
+    pub fixed: bool,
+}
+
+impl Point {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self {
+            x,
+            y,
+            unit: None,
+            fixed: false,
+        }
+    }
+
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub fn fixed(mut self) -> Self {
+        self.fixed = true;
+        self
+    }
+
+    pub fn as_string(&self) -> String {
+        let fixed = if self.fixed { "!" } else { "" };
+        format!("{},{}{fixed}", self.x, self.y)
+    }
+
+    /// Format the point for an attribute that expects `unit`
+    pub(crate) fn as_string_in(&self, unit: Unit) -> String {
+        Self {
+            x: Unit::convert(self.x, self.unit, unit),
+            y: Unit::convert(self.y, self.unit, unit),
+            unit: None,
+            fixed: self.fixed,
+        }
+        .as_string()
+    }
+
+    /// Parse `"x,y"` with an optional trailing `!`, or a single number used for both coordinates
+    pub(crate) fn parse(str: &str) -> Option<Self> {
+        let (str, fixed) = strip_exclamation(str);
+        let (x, y) = parse_pair(str)?;
+        Some(Self {
+            fixed,
+            ..Self::new(x, y)
+        })
+    }
+}
+
+/// A size, written as `"width,height"`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+    pub unit: Option<Unit>,
+
+    /// Scale the drawing up to this size if it's smaller, written as a trailing `!`
+    pub fill: bool,
+}
+
+impl Size {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            unit: None,
+            fill: false,
+        }
+    }
+
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub fn fill(mut self) -> Self {
+        self.fill = true;
+        self
+    }
+
+    pub fn as_string(&self) -> String {
+        let fill = if self.fill { "!" } else { "" };
+        format!("{},{}{fill}", self.width, self.height)
+    }
+
+    /// Format the size for an attribute that expects `unit`
+    pub(crate) fn as_string_in(&self, unit: Unit) -> String {
+        Self {
+            width: Unit::convert(self.width, self.unit, unit),
+            height: Unit::convert(self.height, self.unit, unit),
+            unit: None,
+            fill: self.fill,
+        }
+        .as_string()
+    }
+
+    pub(crate) fn parse(str: &str) -> Option<Self> {
+        let (str, fill) = strip_exclamation(str);
+        let (width, height) = parse_pair(str)?;
+        Some(Self {
+            fill,
+            ..Self::new(width, height)
+        })
+    }
+}
+
+/// A rectangle, written as `"llx,lly,urx,ury"`
+///
+/// The corners are plain coordinates, as a `rect` has no room for the `!` of a fixed `Point`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub lower_left: (f32, f32),
+    pub upper_right: (f32, f32),
+    pub unit: Option<Unit>,
+}
+
+impl Rect {
+    pub fn new(lower_left: (f32, f32), upper_right: (f32, f32)) -> Self {
+        Self {
+            lower_left,
+            upper_right,
+            unit: None,
+        }
+    }
+
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub fn as_string(&self) -> String {
+        let ((llx, lly), (urx, ury)) = (self.lower_left, self.upper_right);
+        format!("{llx},{lly},{urx},{ury}")
+    }
+
+    /// Format the rectangle for an attribute that expects `unit`
+    pub(crate) fn as_string_in(&self, unit: Unit) -> String {
+        let convert = |(x, y): (f32, f32)| {
+            (
+                Unit::convert(x, self.unit, unit),
+                Unit::convert(y, self.unit, unit),
+            )
+        };
+
+        Self::new(convert(self.lower_left), convert(self.upper_right)).as_string()
+    }
+
+    pub(crate) fn parse(str: &str) -> Option<Self> {
+        match parse_numbers(str)?[..] {
+            [llx, lly, urx, ury] => Some(Self::new((llx, lly), (urx, ury))),
+            _ => None,
+        }
+    }
+}
+
+/// The part of the drawing to render, written as `"width,height,zoom,x,y"`
+///
+/// The size and center are plain coordinates, as a viewport has no room for the `!` of a fill
+/// `Size` or a fixed `Point`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    /// The width and height of the final drawing
+    pub size: (f32, f32),
+    pub zoom: f32,
+
+    /// The point in the graph to center on
+    pub center: Option<(f32, f32)>,
+
+    /// The unit of the size and center, which are written in points
+    pub unit: Option<Unit>,
+}
+
+impl Viewport {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            size: (width, height),
+            zoom: 1.0,
+            center: None,
+            unit: None,
+        }
+    }
+
+    pub fn zoom(mut self, zoom: f32) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        self.center = Some((x, y));
+        self
+    }
+
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub fn as_string(&self) -> String {
+        let points = |value| Unit::convert(value, self.unit, Unit::Points);
+
+        let (width, height) = self.size;
+        let mut string = format!("{},{},{}", points(width), points(height), self.zoom);
+        if let Some((x, y)) = self.center {
+            string.push_str(&format!(",{},{}", points(x), points(y)));
+        }
+        string
+    }
+
+    pub(crate) fn parse(str: &str) -> Option<Self> {
+        let viewport = match parse_numbers(str)?[..] {
+            [width, height] => Self::new(width, height),
+            [width, height, zoom] => Self::new(width, height).zoom(zoom),
+            [width, height, zoom, x, y] => Self::new(width, height).zoom(zoom).center(x, y),
+            _ => return None,
+        };
+        Some(viewport)
+    }
+}
+
+/// A string that isn't a valid point, size, rectangle or viewport
+#[derive(Debug, Error, PartialEq)]
+#[error("Invalid {kind} `{value}`")]
+pub struct GeometryParseError {
+    pub kind: &'static str,
+    pub value: String,
+}
+
+impl GeometryParseError {
+    fn new(kind: &'static str, value: &str) -> Self {
+        Self {
+            kind,
+            value: value.to_string(),
+        }
+    }
+}
+
+impl FromStr for Point {
+    type Err = GeometryParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| GeometryParseError::new("point", s))
+    }
+}
+
+impl FromStr for Size {
+    type Err = GeometryParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| GeometryParseError::new("size", s))
+    }
+}
+
+impl FromStr for Rect {
+    type Err = GeometryParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| GeometryParseError::new("rect", s))
+    }
+}
+
+impl FromStr for Viewport {
+    type Err = GeometryParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| GeometryParseError::new("viewport", s))
+    }
+}
+
+fn strip_exclamation(str: &str) -> (&str, bool) {
+    match str.strip_suffix('!') {
+        Some(str) => (str, true),
+        None => (str, false),
+    }
+}
+
+fn parse_numbers(str: &str) -> Option<Vec<f32>> {
+    str.split(',')
+        .map(|number| number.trim().parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .ok()
+}
+
+fn parse_pair(str: &str) -> Option<(f32, f32)> {
+    match parse_numbers(str)?[..] {
+        [both] => Some((both, both)),
+        [x, y] => Some((x, y)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        assert_eq!(Size::new(7.5, 10.0).as_string(), "7.5,10");
+        assert_eq!(Size::new(7.5, 10.0).fill().as_string(), "7.5,10!");
+        assert_eq!(Size::parse("7.5,10!"), Some(Size::new(7.5, 10.0).fill()));
+        assert_eq!(Size::parse("3"), Some(Size::new(3.0, 3.0)));
+        assert_eq!(Size::parse("3,4,5"), None);
+    }
+
+    #[test]
+    fn point() {
+        assert_eq!(Point::new(0.5, 1.0).as_string(), "0.5,1");
+        assert_eq!(Point::new(0.5, 1.0).fixed().as_string(), "0.5,1!");
+        assert_eq!(Point::parse(" 0.5, 1"), Some(Point::new(0.5, 1.0)));
+        assert_eq!(Point::parse("1,2!"), Some(Point::new(1.0, 2.0).fixed()));
+    }
+
+    #[test]
+    fn units() {
+        let point = Point::new(1.0, 0.5).unit(Unit::Inches);
+        assert_eq!(point.as_string_in(Unit::Points), "72,36");
+        assert_eq!(point.as_string_in(Unit::Inches), "1,0.5");
+
+        let size = Size::new(144.0, 36.0).unit(Unit::Points);
+        assert_eq!(size.as_string_in(Unit::Inches), "2,0.5");
+
+        // Without a unit, numbers are taken to be in whatever the attribute expects
+        assert_eq!(Point::new(3.0, 4.0).as_string_in(Unit::Points), "3,4");
+    }
+
+    #[test]
+    fn rect_and_viewport() {
+        let rect = Rect::new((0.0, 0.5), (1.0, 2.0)).unit(Unit::Inches);
+        assert_eq!(rect.as_string(), "0,0.5,1,2");
+        assert_eq!(rect.as_string_in(Unit::Points), "0,36,72,144");
+        assert_eq!(
+            Rect::parse("0,0,72,144"),
+            Some(Rect::new((0.0, 0.0), (72.0, 144.0)))
+        );
+        assert_eq!(Rect::parse("0,0,72"), None);
+
+        let viewport = Viewport::new(5.0, 4.0)
+            .zoom(2.0)
+            .center(1.0, 1.0)
+            .unit(Unit::Inches);
+        assert_eq!(viewport.as_string(), "360,288,2,72,72");
+        assert_eq!(
+            Viewport::parse("400,300,2,72,72"),
+            Some(Viewport::new(400.0, 300.0).zoom(2.0).center(72.0, 72.0))
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("1,2!".parse(), Ok(Point::new(1.0, 2.0).fixed()));
+        assert_eq!("3".parse(), Ok(Size::new(3.0, 3.0)));
+        assert_eq!(
+            "0,0,72,144".parse(),
+            Ok(Rect::new((0.0, 0.0), (72.0, 144.0)))
+        );
+        assert_eq!(
+            "400,300,2".parse(),
+            Ok(Viewport::new(400.0, 300.0).zoom(2.0))
+        );
+        assert_eq!(
+            "1,2,3".parse::<Point>(),
+            Err(GeometryParseError {
+                kind: "point",
+                value: "1,2,3".into()
+            })
+        );
+        assert!("0,0,72".parse::<Rect>().is_err());
+    }
+}
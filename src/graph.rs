@@ -1,15 +1,26 @@
 use crate::{
-    utils::{sanitize, write_attribute, Attribute},
-    Edge, Label, Node,
+    attributes::{Attribute, AttributeValue, Attributes},
+    lexer::Id,
+    parser::parse_bool,
+    utils::{
+        add_styles, bool_string, parse_line_width, parse_styles, sanitize, styles_string,
+        write_attribute, write_attributes,
+    },
+    Color, ColorList, ColorParseError, ColorScheme, Edge, EdgeAttribute, Label, Node,
+    NodeAttribute, Point, Rect, Size, Unit, Viewport,
 };
 use indent_write::io::IndentWriter;
-use std::io;
+use std::{collections::HashMap, fmt::Debug, io};
+use thiserror::Error;
 
-#[derive(Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Graph {
     pub id: Option<String>,
-    cluster: bool,
-    pub attributes: Vec<GraphAttribute>,
+    pub(crate) cluster: bool,
+    pub attributes: Attributes<GraphAttribute>,
+    pub graph_defaults: Attributes<GraphAttribute>,
+    pub node_defaults: Attributes<NodeAttribute>,
+    pub edge_defaults: Attributes<EdgeAttribute>,
     pub subgraphs: Vec<Graph>,
     pub nodes: Vec<Node>,
     pub edges: Vec<Edge>,
@@ -28,11 +39,37 @@ impl Graph {
         self
     }
 
+    /// Like `subgraph`, but fails if `subgraph` sets an attribute that only applies to the root
+    /// graph, which Graphviz would ignore
+    pub fn try_subgraph(mut self, subgraph: Graph) -> Result<Self, SubgraphError> {
+        let root_only = subgraph
+            .attributes
+            .iter()
+            .chain(&subgraph.graph_defaults)
+            .find(|attribute| attribute.is_root_only());
+
+        if let Some(attribute) = root_only {
+            return Err(SubgraphError::RootOnlyAttribute(
+                attribute.key().to_string(),
+            ));
+        }
+
+        self.subgraphs.push(subgraph);
+        Ok(self)
+    }
+
+    /// Draw the subgraph as a cluster, by writing its name with the `cluster` prefix
+    ///
+    /// The prefix is written as `cluster_`, unless the name already starts with `cluster`.
     pub fn cluster(mut self) -> Self {
         self.cluster = true;
         self
     }
 
+    pub fn is_cluster(&self) -> bool {
+        self.cluster
+    }
+
     pub fn node(mut self, node: Node) -> Self {
         self.nodes.push(node);
         self
@@ -43,25 +80,450 @@ impl Graph {
         self
     }
 
+    /// Attributes for this graph that are also inherited by subgraphs, written as `graph [...]`
+    pub fn graph_defaults(mut self, attributes: impl IntoIterator<Item = GraphAttribute>) -> Self {
+        self.graph_defaults.extend(attributes);
+        self
+    }
+
+    /// Attributes for all nodes in this graph and its subgraphs, written as `node [...]`
+    pub fn node_defaults(mut self, attributes: impl IntoIterator<Item = NodeAttribute>) -> Self {
+        self.node_defaults.extend(attributes);
+        self
+    }
+
+    /// Attributes for all edges in this graph and its subgraphs, written as `edge [...]`
+    pub fn edge_defaults(mut self, attributes: impl IntoIterator<Item = EdgeAttribute>) -> Self {
+        self.edge_defaults.extend(attributes);
+        self
+    }
+
     // --- Attributes --- //
 
+    /// The bounding box of the drawing, in points
+    pub fn bb(self, bb: Rect) -> Self {
+        self.attribute(GraphAttribute::Bb(bb))
+    }
+
+    /// Panics if `color` can't be converted, use `try_bg_color` for colors from external input
+    pub fn bg_color<C>(self, color: C) -> Self
+    where
+        C: TryInto<Color>,
+        C::Error: Debug,
+    {
+        let color = color.try_into().unwrap();
+        self.attribute(GraphAttribute::BgColor(color))
+    }
+
+    pub fn try_bg_color<C>(self, color: C) -> Result<Self, ColorParseError>
+    where
+        C: TryInto<Color>,
+        C::Error: Into<ColorParseError>,
+    {
+        let color = color.try_into().map_err(Into::into)?;
+        Ok(self.attribute(GraphAttribute::BgColor(color)))
+    }
+
+    /// The outline color of a cluster, and its fill color if it has no `fillcolor`
+    ///
+    /// Panics if `color` can't be converted, use `try_color` for colors from external input
+    pub fn color<C>(self, color: C) -> Self
+    where
+        C: TryInto<ColorList>,
+        C::Error: Debug,
+    {
+        let color = color.try_into().unwrap();
+        self.attribute(GraphAttribute::Color(color))
+    }
+
+    pub fn try_color<C>(self, color: C) -> Result<Self, ColorParseError>
+    where
+        C: TryInto<ColorList>,
+        C::Error: Into<ColorParseError>,
+    {
+        let color = color.try_into().map_err(Into::into)?;
+        Ok(self.attribute(GraphAttribute::Color(color)))
+    }
+
+    pub fn color_scheme(self, scheme: ColorScheme) -> Self {
+        self.attribute(GraphAttribute::ColorScheme(scheme))
+    }
+
     pub fn compound(self, compound: bool) -> Self {
         self.attribute(GraphAttribute::Compound(compound))
     }
 
+    pub fn concentrate(self, concentrate: bool) -> Self {
+        self.attribute(GraphAttribute::Concentrate(concentrate))
+    }
+
+    pub fn dpi(self, dpi: f32) -> Self {
+        self.attribute(GraphAttribute::Dpi(dpi))
+    }
+
+    /// The fill color of a cluster with the `filled` style
+    ///
+    /// Panics if `color` can't be converted, use `try_fill_color` for colors from external input
+    pub fn fill_color<C>(self, color: C) -> Self
+    where
+        C: TryInto<ColorList>,
+        C::Error: Debug,
+    {
+        let color = color.try_into().unwrap();
+        self.attribute(GraphAttribute::FillColor(color))
+    }
+
+    pub fn try_fill_color<C>(self, color: C) -> Result<Self, ColorParseError>
+    where
+        C: TryInto<ColorList>,
+        C::Error: Into<ColorParseError>,
+    {
+        let color = color.try_into().map_err(Into::into)?;
+        Ok(self.attribute(GraphAttribute::FillColor(color)))
+    }
+
+    /// Panics if `color` can't be converted, use `try_font_color` for colors from external input
+    pub fn font_color<C>(self, color: C) -> Self
+    where
+        C: TryInto<Color>,
+        C::Error: Debug,
+    {
+        let color = color.try_into().unwrap();
+        self.attribute(GraphAttribute::FontColor(color))
+    }
+
+    pub fn try_font_color<C>(self, color: C) -> Result<Self, ColorParseError>
+    where
+        C: TryInto<Color>,
+        C::Error: Into<ColorParseError>,
+    {
+        let color = color.try_into().map_err(Into::into)?;
+        Ok(self.attribute(GraphAttribute::FontColor(color)))
+    }
+
+    pub fn font_name(self, font_name: impl Into<String>) -> Self {
+        self.attribute(GraphAttribute::FontName(font_name.into()))
+    }
+
+    pub fn font_size(self, font_size: f32) -> Self {
+        self.attribute(GraphAttribute::FontSize(font_size))
+    }
+
+    pub fn gradient_angle(self, angle: i32) -> Self {
+        self.attribute(GraphAttribute::GradientAngle(angle))
+    }
+
+    pub fn href(self, href: impl Into<String>) -> Self {
+        self.attribute(GraphAttribute::Href(href.into()))
+    }
+
     pub fn label(self, label: impl Into<Label>) -> Self {
         self.attribute(GraphAttribute::Label(label.into()))
     }
 
-    pub fn margin(self, x: f32, y: f32) -> Self {
-        self.attribute(GraphAttribute::Margin(x, y))
+    pub fn label_just(self, label_just: LabelJust) -> Self {
+        self.attribute(GraphAttribute::LabelJust(label_just))
+    }
+
+    pub fn label_loc(self, label_loc: LabelLoc) -> Self {
+        self.attribute(GraphAttribute::LabelLoc(label_loc))
+    }
+
+    pub fn layout(self, layout: Layout) -> Self {
+        self.attribute(GraphAttribute::Layout(layout))
+    }
+
+    /// The label position, in points
+    pub fn lp(self, lp: Point) -> Self {
+        self.attribute(GraphAttribute::Lp(lp))
+    }
+
+    /// The margin around the drawing, or for clusters, around their nodes
+    ///
+    /// Graphviz reads the margin of the root graph in inches and that of a cluster in points, so
+    /// a margin with a unit is converted to whichever applies.
+    pub fn margin(self, margin: Point) -> Self {
+        self.attribute(GraphAttribute::Margin(margin))
+    }
+
+    pub fn new_rank(self, new_rank: bool) -> Self {
+        self.attribute(GraphAttribute::NewRank(new_rank))
+    }
+
+    pub fn node_sep(self, node_sep: f32) -> Self {
+        self.attribute(GraphAttribute::NodeSep(node_sep))
+    }
+
+    pub fn ordering(self, ordering: Ordering) -> Self {
+        self.attribute(GraphAttribute::Ordering(ordering))
+    }
+
+    pub fn overlap(self, overlap: Overlap) -> Self {
+        self.attribute(GraphAttribute::Overlap(overlap))
+    }
+
+    pub fn pad(self, pad: Point) -> Self {
+        self.attribute(GraphAttribute::Pad(pad))
+    }
+
+    pub fn page(self, page: Size) -> Self {
+        self.attribute(GraphAttribute::Page(page))
+    }
+
+    /// The outline color of a cluster, which takes precedence over `color`
+    ///
+    /// Panics if `color` can't be converted, use `try_pen_color` for colors from external input
+    pub fn pen_color<C>(self, color: C) -> Self
+    where
+        C: TryInto<Color>,
+        C::Error: Debug,
+    {
+        let color = color.try_into().unwrap();
+        self.attribute(GraphAttribute::PenColor(color))
+    }
+
+    pub fn try_pen_color<C>(self, color: C) -> Result<Self, ColorParseError>
+    where
+        C: TryInto<Color>,
+        C::Error: Into<ColorParseError>,
+    {
+        let color = color.try_into().map_err(Into::into)?;
+        Ok(self.attribute(GraphAttribute::PenColor(color)))
+    }
+
+    pub fn pen_width(self, width: f32) -> Self {
+        self.attribute(GraphAttribute::PenWidth(width))
+    }
+
+    pub fn peripheries(self, peripheries: u32) -> Self {
+        self.attribute(GraphAttribute::Peripheries(peripheries))
+    }
+
+    /// Constrain the rank of the nodes in a subgraph, which the `dot` layout ignores for clusters
+    pub fn rank(self, rank: Rank) -> Self {
+        self.attribute(GraphAttribute::Rank(rank))
+    }
+
+    pub fn rank_dir(self, rank_dir: RankDir) -> Self {
+        self.attribute(GraphAttribute::RankDir(rank_dir))
+    }
+
+    pub fn rank_sep(self, rank_sep: f32) -> Self {
+        self.attribute(GraphAttribute::RankSep(rank_sep))
+    }
+
+    pub fn ratio(self, ratio: Ratio) -> Self {
+        self.attribute(GraphAttribute::Ratio(ratio))
+    }
+
+    pub fn size(self, size: Size) -> Self {
+        self.attribute(GraphAttribute::Size(size))
+    }
+
+    pub fn splines(self, splines: Splines) -> Self {
+        self.attribute(GraphAttribute::Splines(splines))
+    }
+
+    /// The `id` attribute, used to identify the graph or cluster in SVG and image map output
+    pub fn svg_id(self, id: impl Into<String>) -> Self {
+        self.attribute(GraphAttribute::Id(id.into()))
+    }
+
+    pub fn target(self, target: impl Into<String>) -> Self {
+        self.attribute(GraphAttribute::Target(target.into()))
+    }
+
+    pub fn tooltip(self, tooltip: impl Into<String>) -> Self {
+        self.attribute(GraphAttribute::Tooltip(tooltip.into()))
     }
 
+    pub fn url(self, url: impl Into<String>) -> Self {
+        self.attribute(GraphAttribute::Url(url.into()))
+    }
+
+    pub fn viewport(self, viewport: Viewport) -> Self {
+        self.attribute(GraphAttribute::Viewport(viewport))
+    }
+
+    /// Add a style, combining it with any styles set before
+    pub fn style(self, style: ClusterStyle) -> Self {
+        self.styles([style])
+    }
+
+    pub fn styles(mut self, styles: impl IntoIterator<Item = ClusterStyle>) -> Self {
+        match self.attributes.get_mut("style") {
+            Some(GraphAttribute::Style(existing)) => add_styles(existing, styles),
+            _ => {
+                let mut new = Vec::new();
+                add_styles(&mut new, styles);
+                self.attributes.set(GraphAttribute::Style(new));
+            }
+        }
+        self
+    }
+
+    /// Set an attribute, replacing any earlier attribute with the same key
     pub fn attribute(mut self, attribute: GraphAttribute) -> Self {
-        self.attributes.push(attribute);
+        self.attributes.set(attribute);
         self
     }
 
+    /// Set an attribute of a user-defined type, see `Attribute` for how to format its value
+    pub fn attr(self, attribute: impl Attribute) -> Self {
+        let (key, value) = attribute.pair();
+        let attribute = GraphAttribute::Unknown(key.to_string(), AttributeValue::Raw(value));
+        self.attribute(attribute)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&GraphAttribute> {
+        self.attributes.get(key)
+    }
+
+    /// Set an attribute after construction, returning the one it replaced, if any
+    pub fn set(&mut self, attribute: GraphAttribute) -> Option<GraphAttribute> {
+        self.attributes.set(attribute)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<GraphAttribute> {
+        self.attributes.remove(key)
+    }
+
+    /// Merge edges between the same pair of nodes, across all subgraphs
+    ///
+    /// The first occurrence is kept, and receives the attributes of its duplicates, which replace
+    /// its own where they share a key. Ports set by a duplicate replace those of the first
+    /// occurrence too, as they are its `tailport` and `headport`.
+    /// In undirected graphs `a -- b` and `b -- a` are considered the same edge. Edge chains that
+    /// share a pair of nodes with another edge are split into single edges first, while edges
+    /// with subgraph endpoints are left as they are.
+    pub fn dedup_edges(&mut self, directed: bool) {
+        let mut counts = HashMap::new();
+        self.count_edge_keys(directed, &mut counts);
+        self.split_duplicate_chains(directed, &counts);
+
+        let mut merged = HashMap::new();
+        self.remove_duplicate_edges(directed, &mut merged);
+        self.merge_edge_attributes(directed, &mut merged);
+    }
+
+    fn count_edge_keys(&self, directed: bool, counts: &mut HashMap<(String, String), usize>) {
+        for subgraph in &self.subgraphs {
+            subgraph.count_edge_keys(directed, counts);
+        }
+
+        for edge in &self.edges {
+            for key in edge.chain_keys(directed).into_iter().flatten() {
+                *counts.entry(key).or_default() += 1;
+            }
+        }
+    }
+
+    fn split_duplicate_chains(
+        &mut self,
+        directed: bool,
+        counts: &HashMap<(String, String), usize>,
+    ) {
+        for subgraph in &mut self.subgraphs {
+            subgraph.split_duplicate_chains(directed, counts);
+        }
+
+        let is_duplicate = |edge: &Edge| {
+            edge.endpoints.len() > 2
+                && edge
+                    .chain_keys(directed)
+                    .into_iter()
+                    .flatten()
+                    .any(|key| counts[&key] > 1)
+        };
+
+        if self.edges.iter().any(is_duplicate) {
+            self.edges = std::mem::take(&mut self.edges)
+                .into_iter()
+                .flat_map(|edge| {
+                    if is_duplicate(&edge) {
+                        edge.split()
+                    } else {
+                        vec![edge]
+                    }
+                })
+                .collect();
+        }
+    }
+
+    fn remove_duplicate_edges(
+        &mut self,
+        directed: bool,
+        merged: &mut HashMap<(String, String), Edge>,
+    ) {
+        for subgraph in &mut self.subgraphs {
+            subgraph.remove_duplicate_edges(directed, merged);
+        }
+
+        self.edges.retain(|edge| {
+            let key = match edge.key(directed) {
+                Some(key) => key,
+                None => return true,
+            };
+
+            match merged.get_mut(&key) {
+                Some(first) => {
+                    first.merge(edge, directed);
+                    false
+                }
+                None => {
+                    merged.insert(key, edge.clone());
+                    true
+                }
+            }
+        });
+    }
+
+    fn merge_edge_attributes(
+        &mut self,
+        directed: bool,
+        merged: &mut HashMap<(String, String), Edge>,
+    ) {
+        for subgraph in &mut self.subgraphs {
+            subgraph.merge_edge_attributes(directed, merged);
+        }
+
+        for edge in &mut self.edges {
+            if let Some(merged) = edge.key(directed).and_then(|key| merged.remove(&key)) {
+                *edge = merged;
+            }
+        }
+    }
+
+    /// Whether this can be written on a single line as `{a b c}`, as an edge endpoint
+    pub(crate) fn is_inline(&self) -> bool {
+        self.id.is_none()
+            && !self.cluster
+            && self.graph_defaults.is_empty()
+            && self.node_defaults.is_empty()
+            && self.edge_defaults.is_empty()
+            && self.subgraphs.is_empty()
+            && self.edges.is_empty()
+            && self.nodes.iter().all(|node| node.attributes.is_empty())
+    }
+
+    pub(crate) fn write_inline(&self, mut w: impl io::Write) -> io::Result<()> {
+        write!(w, "{{")?;
+
+        for attribute in &self.attributes {
+            write_attribute(attribute, &mut w)?;
+            write!(w, "; ")?;
+        }
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            if index > 0 {
+                write!(w, " ")?;
+            }
+            node.write(&mut w)?;
+        }
+
+        write!(w, "}}")
+    }
+
     pub(crate) fn write(
         &self,
         directed: bool,
@@ -69,7 +531,11 @@ impl Graph {
         mut w: impl io::Write,
     ) -> io::Result<()> {
         let cluster = match graph_type {
-            GraphType::Root => {
+            GraphType::Root { strict } => {
+                if strict {
+                    write!(w, "strict ")?
+                }
+
                 if directed {
                     write!(w, "digraph")?
                 } else {
@@ -85,7 +551,7 @@ impl Graph {
         };
 
         if let Some(name) = &self.id {
-            if cluster {
+            if cluster && !name.starts_with("cluster") {
                 let name = format!("cluster_{name}");
                 write!(w, " {}", sanitize(&name))?;
             } else {
@@ -102,6 +568,33 @@ impl Graph {
 
         let mut whitespace = false;
 
+        // Defaults, which only apply to what comes after them
+        if !self.graph_defaults.is_empty() {
+            write!(indented, "graph")?;
+            if cluster {
+                let defaults: Vec<_> = self.graph_defaults.iter().map(ClusterAttribute).collect();
+                write_attributes(defaults.iter(), &mut indented)?;
+            } else {
+                write_attributes(self.graph_defaults.iter(), &mut indented)?;
+            }
+            writeln!(indented)?;
+            whitespace = true;
+        }
+
+        if !self.node_defaults.is_empty() {
+            write!(indented, "node")?;
+            write_attributes(self.node_defaults.iter(), &mut indented)?;
+            writeln!(indented)?;
+            whitespace = true;
+        }
+
+        if !self.edge_defaults.is_empty() {
+            write!(indented, "edge")?;
+            write_attributes(self.edge_defaults.iter(), &mut indented)?;
+            writeln!(indented)?;
+            whitespace = true;
+        }
+
         // Subgraphs
         if !self.subgraphs.is_empty() {
             if whitespace {
@@ -157,7 +650,11 @@ impl Graph {
             }
 
             for attribute in &self.attributes {
-                write_attribute(attribute, &mut indented)?;
+                if cluster {
+                    write_attribute(&ClusterAttribute(attribute), &mut indented)?;
+                } else {
+                    write_attribute(attribute, &mut indented)?;
+                }
                 writeln!(indented)?;
             }
         }
@@ -168,26 +665,602 @@ impl Graph {
     }
 }
 
+#[derive(Debug, Error, PartialEq)]
+pub enum SubgraphError {
+    #[error("`{0}` only applies to the root graph, not to subgraphs")]
+    RootOnlyAttribute(String),
+}
+
 pub(crate) enum GraphType {
-    Root,
+    Root { strict: bool },
     Subgraph { cluster: bool },
 }
 
+/// An attribute of a cluster, which Graphviz reads some units of differently from the root graph
+struct ClusterAttribute<'a>(&'a GraphAttribute);
+
+impl Attribute for ClusterAttribute<'_> {
+    fn pair(&self) -> (&str, String) {
+        match self.0 {
+            GraphAttribute::Margin(margin) => {
+                ("margin", sanitize(&margin.as_string_in(Unit::Points)))
+            }
+            attribute => attribute.pair(),
+        }
+    }
+
+    fn key(&self) -> &str {
+        self.0.key()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum GraphAttribute {
+    Bb(Rect),
+    BgColor(Color),
+    Color(ColorList),
+    ColorScheme(ColorScheme),
     Compound(bool),
+    Concentrate(bool),
+    Dpi(f32),
+    FillColor(ColorList),
+    FontColor(Color),
+    FontName(String),
+    FontSize(f32),
+    GradientAngle(i32),
+    Href(String),
+    Id(String),
     Label(Label),
-    Margin(f32, f32),
+    LabelJust(LabelJust),
+    LabelLoc(LabelLoc),
+    Lp(Point),
+    Layout(Layout),
+    Margin(Point),
+    NewRank(bool),
+    NodeSep(f32),
+    Ordering(Ordering),
+    Overlap(Overlap),
+    Pad(Point),
+    Page(Size),
+    PenColor(Color),
+    PenWidth(f32),
+    Peripheries(u32),
+    Rank(Rank),
+    RankDir(RankDir),
+    RankSep(f32),
+    Ratio(Ratio),
+    Size(Size),
+    Splines(Splines),
+    Target(String),
+    Tooltip(String),
+    Url(String),
+    Viewport(Viewport),
+    Style(Vec<ClusterStyle>),
+    Unknown(String, AttributeValue),
+}
+
+impl GraphAttribute {
+    pub(crate) fn from_pair(key: &str, value: Id) -> Self {
+        let unknown = |value: Id| Self::Unknown(key.to_string(), AttributeValue::from_id(value));
+        let str = value.as_str();
+
+        let attribute = match key {
+            "bb" => Rect::parse(str).map(Self::Bb),
+            "bgcolor" => return Self::BgColor(Color::from_id(value)),
+            "color" => ColorList::from_id(&value).map(Self::Color),
+            "colorscheme" => str.parse().ok().map(Self::ColorScheme),
+            "compound" => parse_bool(str).map(Self::Compound),
+            "concentrate" => parse_bool(str).map(Self::Concentrate),
+            "dpi" | "resolution" => str.parse().ok().map(Self::Dpi),
+            "fillcolor" => ColorList::from_id(&value).map(Self::FillColor),
+            "fontcolor" => return Self::FontColor(Color::from_id(value)),
+            "fontname" => Some(Self::FontName(value.unescaped())),
+            "fontsize" => str.parse().ok().map(Self::FontSize),
+            "gradientangle" => str.parse().ok().map(Self::GradientAngle),
+            "href" => Some(Self::Href(value.unescaped())),
+            "id" => Some(Self::Id(value.unescaped())),
+            "label" => return Self::Label(Label::from_id(value)),
+            "labeljust" => LabelJust::from_name(str).map(Self::LabelJust),
+            "labelloc" => LabelLoc::from_name(str).map(Self::LabelLoc),
+            "lp" => Point::parse(str).map(Self::Lp),
+            "layout" => Layout::from_name(str).map(Self::Layout),
+            "margin" => Point::parse(str).map(Self::Margin),
+            "newrank" => parse_bool(str).map(Self::NewRank),
+            "nodesep" => str.parse().ok().map(Self::NodeSep),
+            "ordering" => Ordering::from_name(str).map(Self::Ordering),
+            "overlap" => Overlap::from_name(str).map(Self::Overlap),
+            "pad" => Point::parse(str).map(Self::Pad),
+            "page" => Size::parse(str).map(Self::Page),
+            "pencolor" => return Self::PenColor(Color::from_id(value)),
+            "penwidth" => str.parse().ok().map(Self::PenWidth),
+            "peripheries" => str.parse().ok().map(Self::Peripheries),
+            "rank" => Rank::from_name(str).map(Self::Rank),
+            "rankdir" => RankDir::from_name(str).map(Self::RankDir),
+            "ranksep" => str.parse().ok().map(Self::RankSep),
+            "ratio" => Ratio::from_name(str).map(Self::Ratio),
+            "size" => Size::parse(str).map(Self::Size),
+            "splines" => Splines::from_name(str).map(Self::Splines),
+            "target" => Some(Self::Target(value.unescaped())),
+            "tooltip" => Some(Self::Tooltip(value.unescaped())),
+            "URL" => Some(Self::Url(value.unescaped())),
+            "viewport" => Viewport::parse(str).map(Self::Viewport),
+            "style" => parse_styles(str, ClusterStyle::from_name).map(Self::Style),
+            _ => None,
+        };
+
+        attribute.unwrap_or_else(|| unknown(value))
+    }
+
+    /// Whether the attribute only has an effect on the root graph, and not on subgraphs
+    pub fn is_root_only(&self) -> bool {
+        matches!(
+            self,
+            Self::Compound(_)
+                | Self::Concentrate(_)
+                | Self::Dpi(_)
+                | Self::Layout(_)
+                | Self::NewRank(_)
+                | Self::NodeSep(_)
+                | Self::Overlap(_)
+                | Self::Pad(_)
+                | Self::Page(_)
+                | Self::RankDir(_)
+                | Self::RankSep(_)
+                | Self::Ratio(_)
+                | Self::Size(_)
+                | Self::Splines(_)
+                | Self::Viewport(_)
+        )
+    }
 }
 
 impl Attribute for GraphAttribute {
     fn pair(&self) -> (&str, String) {
         match self {
-            Self::Compound(compound) => (
-                "compound",
-                String::from(if *compound { "true" } else { "false" }),
-            ),
+            Self::Bb(bb) => ("bb", sanitize(&bb.as_string_in(Unit::Points))),
+            Self::BgColor(color) => ("bgcolor", sanitize(&color.as_string())),
+            Self::Color(color) => ("color", sanitize(&color.as_string())),
+            Self::ColorScheme(scheme) => ("colorscheme", scheme.as_string()),
+            Self::Compound(compound) => ("compound", bool_string(*compound)),
+            Self::Concentrate(concentrate) => ("concentrate", bool_string(*concentrate)),
+            Self::Dpi(dpi) => ("dpi", format!("{dpi}")),
+            Self::FillColor(color) => ("fillcolor", sanitize(&color.as_string())),
+            Self::FontColor(color) => ("fontcolor", sanitize(&color.as_string())),
+            Self::FontName(font_name) => ("fontname", sanitize(font_name)),
+            Self::FontSize(font_size) => ("fontsize", format!("{font_size}")),
+            Self::GradientAngle(angle) => ("gradientangle", format!("{angle}")),
+            Self::Href(href) => ("href", sanitize(href)),
+            Self::Id(id) => ("id", sanitize(id)),
             Self::Label(label) => ("label", label.as_string()),
-            Self::Margin(x, y) => ("margin", format!("\"{x},{y}\"")),
+            Self::LabelJust(label_just) => ("labeljust", label_just.as_str().to_string()),
+            Self::LabelLoc(label_loc) => ("labelloc", label_loc.as_str().to_string()),
+            Self::Lp(lp) => ("lp", sanitize(&lp.as_string_in(Unit::Points))),
+            Self::Layout(layout) => ("layout", layout.as_str().to_string()),
+            Self::Margin(margin) => ("margin", sanitize(&margin.as_string_in(Unit::Inches))),
+            Self::NewRank(new_rank) => ("newrank", bool_string(*new_rank)),
+            Self::NodeSep(node_sep) => ("nodesep", format!("{node_sep}")),
+            Self::Ordering(ordering) => ("ordering", ordering.as_str().to_string()),
+            Self::Overlap(overlap) => ("overlap", overlap.as_str().to_string()),
+            Self::Pad(pad) => ("pad", sanitize(&pad.as_string_in(Unit::Inches))),
+            Self::Page(page) => ("page", sanitize(&page.as_string_in(Unit::Inches))),
+            Self::PenColor(color) => ("pencolor", sanitize(&color.as_string())),
+            Self::PenWidth(width) => ("penwidth", format!("{width}")),
+            Self::Peripheries(peripheries) => ("peripheries", format!("{peripheries}")),
+            Self::Rank(rank) => ("rank", rank.as_str().to_string()),
+            Self::RankDir(rank_dir) => ("rankdir", rank_dir.as_str().to_string()),
+            Self::RankSep(rank_sep) => ("ranksep", format!("{rank_sep}")),
+            Self::Ratio(ratio) => ("ratio", ratio.as_string()),
+            Self::Size(size) => ("size", sanitize(&size.as_string_in(Unit::Inches))),
+            Self::Splines(splines) => ("splines", splines.as_str().to_string()),
+            Self::Target(target) => ("target", sanitize(target)),
+            Self::Tooltip(tooltip) => ("tooltip", sanitize(tooltip)),
+            Self::Url(url) => ("URL", sanitize(url)),
+            Self::Viewport(viewport) => ("viewport", sanitize(&viewport.as_string())),
+            Self::Style(styles) => ("style", styles_string(styles, ClusterStyle::as_string)),
+            Self::Unknown(key, value) => (key, value.as_string()),
+        }
+    }
+
+    fn key(&self) -> &str {
+        match self {
+            Self::Bb(_) => "bb",
+            Self::BgColor(_) => "bgcolor",
+            Self::Color(_) => "color",
+            Self::ColorScheme(_) => "colorscheme",
+            Self::Compound(_) => "compound",
+            Self::Concentrate(_) => "concentrate",
+            Self::Dpi(_) => "dpi",
+            Self::FillColor(_) => "fillcolor",
+            Self::FontColor(_) => "fontcolor",
+            Self::FontName(_) => "fontname",
+            Self::FontSize(_) => "fontsize",
+            Self::GradientAngle(_) => "gradientangle",
+            Self::Href(_) => "href",
+            Self::Id(_) => "id",
+            Self::Label(_) => "label",
+            Self::LabelJust(_) => "labeljust",
+            Self::LabelLoc(_) => "labelloc",
+            Self::Lp(_) => "lp",
+            Self::Layout(_) => "layout",
+            Self::Margin(_) => "margin",
+            Self::NewRank(_) => "newrank",
+            Self::NodeSep(_) => "nodesep",
+            Self::Ordering(_) => "ordering",
+            Self::Overlap(_) => "overlap",
+            Self::Pad(_) => "pad",
+            Self::Page(_) => "page",
+            Self::PenColor(_) => "pencolor",
+            Self::PenWidth(_) => "penwidth",
+            Self::Peripheries(_) => "peripheries",
+            Self::Rank(_) => "rank",
+            Self::RankDir(_) => "rankdir",
+            Self::RankSep(_) => "ranksep",
+            Self::Ratio(_) => "ratio",
+            Self::Size(_) => "size",
+            Self::Splines(_) => "splines",
+            Self::Target(_) => "target",
+            Self::Tooltip(_) => "tooltip",
+            Self::Url(_) => "URL",
+            Self::Viewport(_) => "viewport",
+            Self::Style(_) => "style",
+            Self::Unknown(key, _) => key,
+        }
+    }
+}
+
+/// The direction in which ranks are laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankDir {
+    TopToBottom,
+    LeftToRight,
+    BottomToTop,
+    RightToLeft,
+}
+
+impl RankDir {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TopToBottom => "TB",
+            Self::LeftToRight => "LR",
+            Self::BottomToTop => "BT",
+            Self::RightToLeft => "RL",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "TB" => Some(Self::TopToBottom),
+            "LR" => Some(Self::LeftToRight),
+            "BT" => Some(Self::BottomToTop),
+            "RL" => Some(Self::RightToLeft),
+            _ => None,
+        }
+    }
+}
+
+/// How the nodes of a subgraph are ranked relative to each other and the rest of the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rank {
+    /// All nodes on the same rank
+    Same,
+
+    /// All nodes on the minimum rank
+    Min,
+
+    /// All nodes on the minimum rank, which only nodes of other `Source` subgraphs share
+    Source,
+
+    /// All nodes on the maximum rank
+    Max,
+
+    /// All nodes on the maximum rank, which only nodes of other `Sink` subgraphs share
+    Sink,
+}
+
+impl Rank {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Same => "same",
+            Self::Min => "min",
+            Self::Source => "source",
+            Self::Max => "max",
+            Self::Sink => "sink",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "same" => Some(Self::Same),
+            "min" => Some(Self::Min),
+            "source" => Some(Self::Source),
+            "max" => Some(Self::Max),
+            "sink" => Some(Self::Sink),
+            _ => None,
+        }
+    }
+}
+
+/// The style of a cluster, or `radial` for the gradient fill of the root graph
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClusterStyle {
+    Bold,
+    Dashed,
+    Dotted,
+    Filled,
+    Invisible,
+    Radial,
+    Rounded,
+    Solid,
+    Striped,
+
+    /// Deprecated by Graphviz in favor of `penwidth`, but still understood
+    SetLineWidth(f32),
+}
+
+impl ClusterStyle {
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "bold" => Some(Self::Bold),
+            "dashed" => Some(Self::Dashed),
+            "dotted" => Some(Self::Dotted),
+            "filled" => Some(Self::Filled),
+            "invis" => Some(Self::Invisible),
+            "radial" => Some(Self::Radial),
+            "rounded" => Some(Self::Rounded),
+            "solid" => Some(Self::Solid),
+            "striped" => Some(Self::Striped),
+            _ => parse_line_width(name).map(Self::SetLineWidth),
+        }
+    }
+
+    pub fn as_string(&self) -> String {
+        let str = match self {
+            Self::Bold => "bold",
+            Self::Dashed => "dashed",
+            Self::Dotted => "dotted",
+            Self::Filled => "filled",
+            Self::Invisible => "invis",
+            Self::Radial => "radial",
+            Self::Rounded => "rounded",
+            Self::Solid => "solid",
+            Self::Striped => "striped",
+            Self::SetLineWidth(width) => return format!("setlinewidth({width})"),
+        };
+        str.to_string()
+    }
+}
+
+/// How edges are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Splines {
+    None,
+    Line,
+    Polyline,
+    Curved,
+    Ortho,
+    Spline,
+    Compound,
+}
+
+impl Splines {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Line => "line",
+            Self::Polyline => "polyline",
+            Self::Curved => "curved",
+            Self::Ortho => "ortho",
+            Self::Spline => "spline",
+            Self::Compound => "compound",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "none" | "" => Some(Self::None),
+            "line" | "false" => Some(Self::Line),
+            "polyline" => Some(Self::Polyline),
+            "curved" => Some(Self::Curved),
+            "ortho" => Some(Self::Ortho),
+            "spline" | "true" => Some(Self::Spline),
+            "compound" => Some(Self::Compound),
+            _ => None,
+        }
+    }
+}
+
+/// How the drawing is scaled to fit `size`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ratio {
+    Fill,
+    Compress,
+    Expand,
+    Auto,
+
+    /// A desired aspect ratio of height over width
+    Aspect(f32),
+}
+
+impl Ratio {
+    pub fn as_string(&self) -> String {
+        match self {
+            Self::Fill => String::from("fill"),
+            Self::Compress => String::from("compress"),
+            Self::Expand => String::from("expand"),
+            Self::Auto => String::from("auto"),
+            Self::Aspect(aspect) => format!("{aspect}"),
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fill" => Some(Self::Fill),
+            "compress" => Some(Self::Compress),
+            "expand" => Some(Self::Expand),
+            "auto" => Some(Self::Auto),
+            name => name.parse().ok().map(Self::Aspect),
+        }
+    }
+}
+
+/// The layout engine used to render the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    Dot,
+    Neato,
+    Fdp,
+    Sfdp,
+    Circo,
+    Twopi,
+    Osage,
+    Patchwork,
+}
+
+impl Layout {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::Neato => "neato",
+            Self::Fdp => "fdp",
+            Self::Sfdp => "sfdp",
+            Self::Circo => "circo",
+            Self::Twopi => "twopi",
+            Self::Osage => "osage",
+            Self::Patchwork => "patchwork",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dot" => Some(Self::Dot),
+            "neato" => Some(Self::Neato),
+            "fdp" => Some(Self::Fdp),
+            "sfdp" => Some(Self::Sfdp),
+            "circo" => Some(Self::Circo),
+            "twopi" => Some(Self::Twopi),
+            "osage" => Some(Self::Osage),
+            "patchwork" => Some(Self::Patchwork),
+            _ => None,
+        }
+    }
+}
+
+/// How overlapping nodes are removed, for layouts other than `dot`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overlap {
+    Retain,
+    Remove,
+    Scale,
+    ScaleXY,
+    Prism,
+    Compress,
+    Vpsc,
+    Ortho,
+}
+
+impl Overlap {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Retain => "true",
+            Self::Remove => "false",
+            Self::Scale => "scale",
+            Self::ScaleXY => "scalexy",
+            Self::Prism => "prism",
+            Self::Compress => "compress",
+            Self::Vpsc => "vpsc",
+            Self::Ortho => "ortho",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "true" => Some(Self::Retain),
+            "false" => Some(Self::Remove),
+            "scale" => Some(Self::Scale),
+            "scalexy" => Some(Self::ScaleXY),
+            "prism" => Some(Self::Prism),
+            "compress" => Some(Self::Compress),
+            "vpsc" => Some(Self::Vpsc),
+            "ortho" => Some(Self::Ortho),
+            _ => None,
+        }
+    }
+}
+
+/// Keep the order of edges around nodes as they appear in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ordering {
+    In,
+    Out,
+}
+
+impl Ordering {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::In => "in",
+            Self::Out => "out",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "in" => Some(Self::In),
+            "out" => Some(Self::Out),
+            _ => None,
+        }
+    }
+}
+
+/// The vertical placement of a graph or cluster label
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelLoc {
+    Top,
+    Center,
+    Bottom,
+}
+
+impl LabelLoc {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Top => "t",
+            Self::Center => "c",
+            Self::Bottom => "b",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "t" => Some(Self::Top),
+            "c" => Some(Self::Center),
+            "b" => Some(Self::Bottom),
+            _ => None,
+        }
+    }
+}
+
+/// The horizontal justification of a graph or cluster label
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelJust {
+    Left,
+    Center,
+    Right,
+}
+
+impl LabelJust {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Left => "l",
+            Self::Center => "c",
+            Self::Right => "r",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "l" => Some(Self::Left),
+            "c" => Some(Self::Center),
+            "r" => Some(Self::Right),
+            _ => None,
         }
     }
 }
@@ -0,0 +1,911 @@
+use crate::{Color, Label};
+use std::fmt::Write;
+use thiserror::Error;
+
+/// An HTML-like label, either formatted text or a table
+///
+/// Text is escaped when written, and the structure is validated against Graphviz' HTML grammar
+/// when converting into a `Label`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HtmlLabel {
+    Text(HtmlText),
+    Table(HtmlTable),
+}
+
+impl HtmlLabel {
+    pub fn validate(&self) -> Result<(), HtmlError> {
+        match self {
+            // Text can't contain tables, so any combination of items is valid
+            Self::Text(_) => Ok(()),
+            Self::Table(table) => table.validate(),
+        }
+    }
+
+    /// The label as a DOT `ID`, including the outer angle brackets
+    pub fn as_string(&self) -> Result<String, HtmlError> {
+        self.validate()?;
+
+        let mut string = String::from("<");
+        self.write(&mut string);
+        string.push('>');
+        Ok(string)
+    }
+
+    fn write(&self, string: &mut String) {
+        match self {
+            Self::Text(text) => text.write(string),
+            Self::Table(table) => table.write(string),
+        }
+    }
+}
+
+impl From<HtmlText> for HtmlLabel {
+    fn from(text: HtmlText) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<HtmlTable> for HtmlLabel {
+    fn from(table: HtmlTable) -> Self {
+        Self::Table(table)
+    }
+}
+
+impl TryFrom<HtmlLabel> for Label {
+    type Error = HtmlError;
+
+    fn try_from(label: HtmlLabel) -> Result<Self, Self::Error> {
+        Ok(Label::HtmlLike(label.as_string()?))
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum HtmlError {
+    #[error("A table needs at least one row")]
+    EmptyTable,
+
+    #[error("A row needs at least one cell")]
+    EmptyRow,
+
+    #[error("Rules can only be placed between rows or cells")]
+    MisplacedRule,
+
+    #[error("Tables can't be wrapped in <{0}>")]
+    InvalidTableFormat(&'static str),
+
+    #[error("Column and row spans must be at least 1")]
+    ZeroSpan,
+
+    #[error("{0} must be between {1} and {2}")]
+    OutOfRange(&'static str, i64, i64),
+
+    #[error("<{1}> has no attribute {0}")]
+    UnknownAttribute(&'static str, &'static str),
+}
+
+// --- Text --- //
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HtmlText {
+    pub items: Vec<TextItem>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextItem {
+    Text(String),
+    Break(Option<Align>),
+    Font(Font, HtmlText),
+    Format(Format, HtmlText),
+}
+
+impl HtmlText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.items.push(TextItem::Text(text.into()));
+        self
+    }
+
+    /// A line break, written as `<BR/>`
+    pub fn line_break(mut self, align: Option<Align>) -> Self {
+        self.items.push(TextItem::Break(align));
+        self
+    }
+
+    pub fn font(mut self, font: Font, text: HtmlText) -> Self {
+        self.items.push(TextItem::Font(font, text));
+        self
+    }
+
+    pub fn format(mut self, format: Format, text: HtmlText) -> Self {
+        self.items.push(TextItem::Format(format, text));
+        self
+    }
+
+    pub fn bold(self, text: HtmlText) -> Self {
+        self.format(Format::Bold, text)
+    }
+
+    pub fn italic(self, text: HtmlText) -> Self {
+        self.format(Format::Italic, text)
+    }
+
+    pub fn underline(self, text: HtmlText) -> Self {
+        self.format(Format::Underline, text)
+    }
+
+    fn write(&self, string: &mut String) {
+        for item in &self.items {
+            match item {
+                TextItem::Text(text) => escape(text, string),
+                TextItem::Break(None) => string.push_str("<BR/>"),
+                TextItem::Break(Some(align)) => {
+                    write!(string, "<BR ALIGN=\"{}\"/>", align.as_str()).unwrap()
+                }
+                TextItem::Font(font, text) => {
+                    font.write_open(string);
+                    text.write(string);
+                    string.push_str("</FONT>");
+                }
+                TextItem::Format(format, text) => {
+                    write!(string, "<{}>", format.as_str()).unwrap();
+                    text.write(string);
+                    write!(string, "</{}>", format.as_str()).unwrap();
+                }
+            }
+        }
+    }
+}
+
+impl From<&str> for HtmlText {
+    fn from(text: &str) -> Self {
+        Self::new().text(text)
+    }
+}
+
+impl From<String> for HtmlText {
+    fn from(text: String) -> Self {
+        Self::new().text(text)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Font {
+    pub face: Option<String>,
+    pub color: Option<Color>,
+    pub point_size: Option<f32>,
+}
+
+impl Font {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn face(mut self, face: impl Into<String>) -> Self {
+        self.face = Some(face.into());
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn point_size(mut self, point_size: f32) -> Self {
+        self.point_size = Some(point_size);
+        self
+    }
+
+    fn write_open(&self, string: &mut String) {
+        string.push_str("<FONT");
+        if let Some(face) = &self.face {
+            write_attribute("FACE", face, string);
+        }
+        if let Some(color) = &self.color {
+            write_attribute("COLOR", &color.as_string(), string);
+        }
+        if let Some(point_size) = self.point_size {
+            write_attribute("POINT-SIZE", &point_size.to_string(), string);
+        }
+        string.push('>');
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Bold,
+    Italic,
+    Underline,
+    Overline,
+    Subscript,
+    Superscript,
+    Strikethrough,
+}
+
+impl Format {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bold => "B",
+            Self::Italic => "I",
+            Self::Underline => "U",
+            Self::Overline => "O",
+            Self::Subscript => "SUB",
+            Self::Superscript => "SUP",
+            Self::Strikethrough => "S",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Center,
+    Left,
+    Right,
+}
+
+impl Align {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Center => "CENTER",
+            Self::Left => "LEFT",
+            Self::Right => "RIGHT",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerticalAlign {
+    Middle,
+    Bottom,
+    Top,
+}
+
+impl VerticalAlign {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Middle => "MIDDLE",
+            Self::Bottom => "BOTTOM",
+            Self::Top => "TOP",
+        }
+    }
+}
+
+// --- Tables --- //
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HtmlTable {
+    /// Fonts and formats wrapped around the table, outermost first
+    pub wrappers: Vec<TableWrapper>,
+    pub attributes: Vec<(&'static str, String)>,
+    pub rows: Vec<TableItem<TableRow>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableWrapper {
+    Font(Font),
+    Format(Format),
+}
+
+/// Either an entry of a table or row, or a rule (`<HR/>` or `<VR/>`) separating two of them
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableItem<T> {
+    Item(T),
+    Rule,
+}
+
+impl HtmlTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn row(mut self, row: TableRow) -> Self {
+        self.rows.push(TableItem::Item(row));
+        self
+    }
+
+    /// A horizontal rule between the previous and next row
+    pub fn rule(mut self) -> Self {
+        self.rows.push(TableItem::Rule);
+        self
+    }
+
+    pub fn font(mut self, font: Font) -> Self {
+        self.wrappers.push(TableWrapper::Font(font));
+        self
+    }
+
+    /// Wrap the table in a format, which can only be bold, italic, underline or overline
+    pub fn format(mut self, format: Format) -> Self {
+        self.wrappers.push(TableWrapper::Format(format));
+        self
+    }
+
+    // --- Attributes --- //
+
+    pub fn align(self, align: Align) -> Self {
+        self.attribute("ALIGN", align.as_str())
+    }
+
+    pub fn valign(self, valign: VerticalAlign) -> Self {
+        self.attribute("VALIGN", valign.as_str())
+    }
+
+    pub fn bgcolor(self, color: Color) -> Self {
+        self.attribute("BGCOLOR", color.as_string())
+    }
+
+    pub fn color(self, color: Color) -> Self {
+        self.attribute("COLOR", color.as_string())
+    }
+
+    pub fn border(self, border: u8) -> Self {
+        self.attribute("BORDER", border.to_string())
+    }
+
+    /// The border of every cell, which Graphviz only accepts up to 127
+    pub fn cell_border(self, border: u8) -> Self {
+        self.attribute("CELLBORDER", border.to_string())
+    }
+
+    pub fn cell_padding(self, padding: u8) -> Self {
+        self.attribute("CELLPADDING", padding.to_string())
+    }
+
+    pub fn cell_spacing(self, spacing: i8) -> Self {
+        self.attribute("CELLSPACING", spacing.to_string())
+    }
+
+    pub fn width(self, width: u16) -> Self {
+        self.attribute("WIDTH", width.to_string())
+    }
+
+    pub fn height(self, height: u16) -> Self {
+        self.attribute("HEIGHT", height.to_string())
+    }
+
+    pub fn port(self, port: impl Into<String>) -> Self {
+        self.attribute("PORT", port)
+    }
+
+    pub fn href(self, href: impl Into<String>) -> Self {
+        self.attribute("HREF", href)
+    }
+
+    pub fn tooltip(self, tooltip: impl Into<String>) -> Self {
+        self.attribute("TOOLTIP", tooltip)
+    }
+
+    /// Set an attribute, replacing any earlier value for the same key
+    pub fn attribute(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        set_attribute(&mut self.attributes, key, value.into());
+        self
+    }
+
+    fn validate(&self) -> Result<(), HtmlError> {
+        for wrapper in &self.wrappers {
+            if let TableWrapper::Format(
+                format @ (Format::Subscript | Format::Superscript | Format::Strikethrough),
+            ) = wrapper
+            {
+                return Err(HtmlError::InvalidTableFormat(format.as_str()));
+            }
+        }
+
+        validate_attributes("TABLE", TABLE_ATTRIBUTES, &self.attributes)?;
+
+        let rows = validate_items(&self.rows, HtmlError::EmptyTable)?;
+        for row in rows {
+            let cells = validate_items(&row.cells, HtmlError::EmptyRow)?;
+            for cell in cells {
+                cell.validate()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write(&self, string: &mut String) {
+        for wrapper in &self.wrappers {
+            match wrapper {
+                TableWrapper::Font(font) => font.write_open(string),
+                TableWrapper::Format(format) => write!(string, "<{}>", format.as_str()).unwrap(),
+            }
+        }
+
+        string.push_str("<TABLE");
+        for (key, value) in &self.attributes {
+            write_attribute(key, value, string);
+        }
+        string.push('>');
+
+        for row in &self.rows {
+            match row {
+                TableItem::Item(row) => row.write(string),
+                TableItem::Rule => string.push_str("<HR/>"),
+            }
+        }
+
+        string.push_str("</TABLE>");
+
+        for wrapper in self.wrappers.iter().rev() {
+            match wrapper {
+                TableWrapper::Font(_) => string.push_str("</FONT>"),
+                TableWrapper::Format(format) => write!(string, "</{}>", format.as_str()).unwrap(),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableRow {
+    pub cells: Vec<TableItem<TableCell>>,
+}
+
+impl TableRow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cell(mut self, cell: TableCell) -> Self {
+        self.cells.push(TableItem::Item(cell));
+        self
+    }
+
+    /// A vertical rule between the previous and next cell
+    pub fn rule(mut self) -> Self {
+        self.cells.push(TableItem::Rule);
+        self
+    }
+
+    fn write(&self, string: &mut String) {
+        string.push_str("<TR>");
+        for cell in &self.cells {
+            match cell {
+                TableItem::Item(cell) => cell.write(string),
+                TableItem::Rule => string.push_str("<VR/>"),
+            }
+        }
+        string.push_str("</TR>");
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableCell {
+    pub attributes: Vec<(&'static str, String)>,
+    pub content: CellContent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellContent {
+    Label(HtmlLabel),
+    Image(Image),
+}
+
+impl TableCell {
+    pub fn new(content: impl Into<HtmlLabel>) -> Self {
+        Self {
+            attributes: Vec::new(),
+            content: CellContent::Label(content.into()),
+        }
+    }
+
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::new(HtmlText::new().text(text))
+    }
+
+    pub fn image(image: Image) -> Self {
+        Self {
+            attributes: Vec::new(),
+            content: CellContent::Image(image),
+        }
+    }
+
+    // --- Attributes --- //
+
+    pub fn align(self, align: Align) -> Self {
+        self.attribute("ALIGN", align.as_str())
+    }
+
+    /// The alignment of the lines within the cell's text
+    pub fn balign(self, align: Align) -> Self {
+        self.attribute("BALIGN", align.as_str())
+    }
+
+    pub fn valign(self, valign: VerticalAlign) -> Self {
+        self.attribute("VALIGN", valign.as_str())
+    }
+
+    pub fn bgcolor(self, color: Color) -> Self {
+        self.attribute("BGCOLOR", color.as_string())
+    }
+
+    pub fn color(self, color: Color) -> Self {
+        self.attribute("COLOR", color.as_string())
+    }
+
+    pub fn border(self, border: u8) -> Self {
+        self.attribute("BORDER", border.to_string())
+    }
+
+    pub fn cell_padding(self, padding: u8) -> Self {
+        self.attribute("CELLPADDING", padding.to_string())
+    }
+
+    pub fn cell_spacing(self, spacing: i8) -> Self {
+        self.attribute("CELLSPACING", spacing.to_string())
+    }
+
+    pub fn colspan(self, span: u16) -> Self {
+        self.attribute("COLSPAN", span.to_string())
+    }
+
+    pub fn rowspan(self, span: u16) -> Self {
+        self.attribute("ROWSPAN", span.to_string())
+    }
+
+    pub fn width(self, width: u16) -> Self {
+        self.attribute("WIDTH", width.to_string())
+    }
+
+    pub fn height(self, height: u16) -> Self {
+        self.attribute("HEIGHT", height.to_string())
+    }
+
+    pub fn port(self, port: impl Into<String>) -> Self {
+        self.attribute("PORT", port)
+    }
+
+    pub fn href(self, href: impl Into<String>) -> Self {
+        self.attribute("HREF", href)
+    }
+
+    pub fn tooltip(self, tooltip: impl Into<String>) -> Self {
+        self.attribute("TOOLTIP", tooltip)
+    }
+
+    /// Set an attribute, replacing any earlier value for the same key
+    pub fn attribute(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        set_attribute(&mut self.attributes, key, value.into());
+        self
+    }
+
+    fn validate(&self) -> Result<(), HtmlError> {
+        validate_attributes("TD", CELL_ATTRIBUTES, &self.attributes)?;
+
+        match &self.content {
+            CellContent::Label(label) => label.validate(),
+            CellContent::Image(_) => Ok(()),
+        }
+    }
+
+    fn write(&self, string: &mut String) {
+        string.push_str("<TD");
+        for (key, value) in &self.attributes {
+            write_attribute(key, value, string);
+        }
+        string.push('>');
+
+        match &self.content {
+            CellContent::Label(label) => label.write(string),
+            CellContent::Image(image) => image.write(string),
+        }
+
+        string.push_str("</TD>");
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub src: String,
+    pub scale: Option<ImageScale>,
+}
+
+impl Image {
+    pub fn new(src: impl Into<String>) -> Self {
+        Self {
+            src: src.into(),
+            scale: None,
+        }
+    }
+
+    pub fn scale(mut self, scale: ImageScale) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    fn write(&self, string: &mut String) {
+        string.push_str("<IMG");
+        write_attribute("SRC", &self.src, string);
+        if let Some(scale) = &self.scale {
+            write_attribute("SCALE", scale.as_str(), string);
+        }
+        string.push_str("/>");
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageScale {
+    False,
+    True,
+    Width,
+    Height,
+    Both,
+}
+
+impl ImageScale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::False => "FALSE",
+            Self::True => "TRUE",
+            Self::Width => "WIDTH",
+            Self::Height => "HEIGHT",
+            Self::Both => "BOTH",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "false" => Some(Self::False),
+            "true" => Some(Self::True),
+            "width" => Some(Self::Width),
+            "height" => Some(Self::Height),
+            "both" => Some(Self::Both),
+            _ => None,
+        }
+    }
+}
+
+// --- Helpers --- //
+
+/// Check that a list has at least one item, and that rules only sit between items
+fn validate_items<T>(items: &[TableItem<T>], empty: HtmlError) -> Result<Vec<&T>, HtmlError> {
+    let mut previous_rule = true;
+    let mut result = Vec::new();
+
+    for item in items {
+        match item {
+            TableItem::Item(item) => {
+                result.push(item);
+                previous_rule = false;
+            }
+            TableItem::Rule if previous_rule => return Err(HtmlError::MisplacedRule),
+            TableItem::Rule => previous_rule = true,
+        }
+    }
+
+    if result.is_empty() {
+        Err(empty)
+    } else if previous_rule {
+        Err(HtmlError::MisplacedRule)
+    } else {
+        Ok(result)
+    }
+}
+
+const TABLE_ATTRIBUTES: &[&str] = &[
+    "ALIGN",
+    "BGCOLOR",
+    "BORDER",
+    "CELLBORDER",
+    "CELLPADDING",
+    "CELLSPACING",
+    "COLOR",
+    "COLUMNS",
+    "FIXEDSIZE",
+    "GRADIENTANGLE",
+    "HEIGHT",
+    "HREF",
+    "ID",
+    "PORT",
+    "ROWS",
+    "SIDES",
+    "STYLE",
+    "TARGET",
+    "TITLE",
+    "TOOLTIP",
+    "VALIGN",
+    "WIDTH",
+];
+
+const CELL_ATTRIBUTES: &[&str] = &[
+    "ALIGN",
+    "BALIGN",
+    "BGCOLOR",
+    "BORDER",
+    "CELLPADDING",
+    "CELLSPACING",
+    "COLOR",
+    "COLSPAN",
+    "FIXEDSIZE",
+    "GRADIENTANGLE",
+    "HEIGHT",
+    "HREF",
+    "ID",
+    "PORT",
+    "ROWSPAN",
+    "SIDES",
+    "STYLE",
+    "TARGET",
+    "TITLE",
+    "TOOLTIP",
+    "VALIGN",
+    "WIDTH",
+];
+
+/// Check attributes against the ones Graphviz allows on `element`, which it matches ignoring case
+fn validate_attributes(
+    element: &'static str,
+    allowed: &[&str],
+    attributes: &[(&'static str, String)],
+) -> Result<(), HtmlError> {
+    for (key, value) in attributes {
+        if !allowed
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(key))
+        {
+            return Err(HtmlError::UnknownAttribute(key, element));
+        }
+
+        let range = match key.to_ascii_uppercase().as_str() {
+            "COLSPAN" | "ROWSPAN" if value == "0" => return Err(HtmlError::ZeroSpan),
+            "COLSPAN" | "ROWSPAN" | "WIDTH" | "HEIGHT" => 0..=i64::from(u16::MAX),
+            "BORDER" | "CELLPADDING" => 0..=i64::from(u8::MAX),
+            "CELLBORDER" => 0..=i64::from(i8::MAX),
+            "CELLSPACING" => i64::from(i8::MIN)..=i64::from(i8::MAX),
+            _ => continue,
+        };
+
+        if !value.parse().is_ok_and(|value| range.contains(&value)) {
+            return Err(HtmlError::OutOfRange(key, *range.start(), *range.end()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Set an attribute in place if it's already there, matching keys ignoring case like Graphviz
+fn set_attribute(attributes: &mut Vec<(&'static str, String)>, key: &'static str, value: String) {
+    match attributes
+        .iter_mut()
+        .find(|(existing, _)| existing.eq_ignore_ascii_case(key))
+    {
+        Some(attribute) => *attribute = (key, value),
+        None => attributes.push((key, value)),
+    }
+}
+
+fn write_attribute(key: &str, value: &str, string: &mut String) {
+    write!(string, " {key}=\"").unwrap();
+    escape(value, string);
+    string.push('"');
+}
+
+fn escape(text: &str, string: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => string.push_str("&amp;"),
+            '<' => string.push_str("&lt;"),
+            '>' => string.push_str("&gt;"),
+            '"' => string.push_str("&quot;"),
+            c => string.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text() {
+        let label = HtmlLabel::from(
+            HtmlText::new()
+                .text("a & b < c")
+                .line_break(Some(Align::Left))
+                .bold(HtmlText::new().italic("x".into()))
+                .font(Font::new().face("Helvetica").point_size(10.0), "y".into()),
+        );
+
+        assert_eq!(
+            label.as_string(),
+            Ok(String::from(
+                "<a &amp; b &lt; c<BR ALIGN=\"LEFT\"/><B><I>x</I></B><FONT FACE=\"Helvetica\" POINT-SIZE=\"10\">y</FONT>>"
+            ))
+        );
+    }
+
+    #[test]
+    fn table() {
+        let table = HtmlTable::new()
+            .border(0)
+            .format(Format::Bold)
+            .row(
+                TableRow::new()
+                    .cell(TableCell::text("\"a\"").port("p").colspan(2))
+                    .rule()
+                    .cell(TableCell::image(
+                        Image::new("x.png").scale(ImageScale::Both),
+                    )),
+            )
+            .rule()
+            .row(TableRow::new().cell(TableCell::new(HtmlTable::new().row(
+                TableRow::new().cell(TableCell::text("nested").bgcolor(Color::Name("red".into()))),
+            ))));
+
+        let label = Label::try_from(HtmlLabel::from(table)).unwrap();
+        assert_eq!(
+            label,
+            Label::HtmlLike(String::from(
+                "<<B><TABLE BORDER=\"0\"><TR><TD PORT=\"p\" COLSPAN=\"2\">&quot;a&quot;</TD><VR/><TD><IMG SRC=\"x.png\" SCALE=\"BOTH\"/></TD></TR><HR/><TR><TD><TABLE><TR><TD BGCOLOR=\"red\">nested</TD></TR></TABLE></TD></TR></TABLE></B>>"
+            ))
+        );
+    }
+
+    #[test]
+    fn validation() {
+        let cell = || TableCell::text("a");
+        let row = || TableRow::new().cell(cell());
+
+        assert_eq!(
+            HtmlLabel::from(HtmlTable::new()).validate(),
+            Err(HtmlError::EmptyTable)
+        );
+        assert_eq!(
+            HtmlLabel::from(HtmlTable::new().row(TableRow::new())).validate(),
+            Err(HtmlError::EmptyRow)
+        );
+        assert_eq!(
+            HtmlLabel::from(HtmlTable::new().rule().row(row())).validate(),
+            Err(HtmlError::MisplacedRule)
+        );
+        assert_eq!(
+            HtmlLabel::from(HtmlTable::new().row(row().rule())).validate(),
+            Err(HtmlError::MisplacedRule)
+        );
+        assert_eq!(
+            HtmlLabel::from(HtmlTable::new().row(row()).format(Format::Subscript)).validate(),
+            Err(HtmlError::InvalidTableFormat("SUB"))
+        );
+        assert_eq!(
+            HtmlLabel::from(HtmlTable::new().row(TableRow::new().cell(cell().rowspan(0))))
+                .validate(),
+            Err(HtmlError::ZeroSpan)
+        );
+        assert_eq!(
+            HtmlLabel::from(HtmlTable::new().border(200).row(row())).validate(),
+            Ok(())
+        );
+        assert_eq!(
+            HtmlLabel::from(HtmlTable::new().cell_border(127).row(row())).validate(),
+            Ok(())
+        );
+        assert_eq!(
+            HtmlLabel::from(HtmlTable::new().cell_border(200).row(row())).validate(),
+            Err(HtmlError::OutOfRange("CELLBORDER", 0, 127))
+        );
+        assert_eq!(
+            HtmlLabel::from(HtmlTable::new().cell_spacing(-2).row(row())).validate(),
+            Ok(())
+        );
+        assert_eq!(
+            HtmlLabel::from(HtmlTable::new().attribute("CELLSPACING", "-129").row(row()))
+                .validate(),
+            Err(HtmlError::OutOfRange("CELLSPACING", -128, 127))
+        );
+        assert_eq!(
+            HtmlLabel::from(
+                HtmlTable::new().row(TableRow::new().cell(cell().attribute("ROWS", "*")))
+            )
+            .validate(),
+            Err(HtmlError::UnknownAttribute("ROWS", "TD"))
+        );
+
+        let table = HtmlTable::new()
+            .border(1)
+            .attribute("border", "2")
+            .border(3);
+        assert_eq!(table.attributes, [("BORDER", "3".to_string())]);
+    }
+}
@@ -1,16 +1,26 @@
-use crate::utils::sanitize;
+use crate::{lexer::Id, utils::esc_string, RecordLabel};
 
+/// A label, where text is an escString: escapes like `\l` and `\N` are kept for Graphviz
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Label {
     Text(String),
     HtmlLike(String),
+    Record(RecordLabel),
 }
 
 impl Label {
     pub fn as_string(&self) -> String {
         match self {
-            Self::Text(string) => sanitize(string),
+            Self::Text(string) => esc_string(string),
             Self::HtmlLike(string) => string.clone(),
+            Self::Record(record) => record.as_string(),
+        }
+    }
+
+    pub(crate) fn from_id(id: Id) -> Self {
+        match id {
+            Id::Html(string) => Self::HtmlLike(format!("<{string}>")),
+            id => Self::Text(id.into_esc_string()),
         }
     }
 }
@@ -24,6 +34,12 @@ where
     }
 }
 
+impl From<RecordLabel> for Label {
+    fn from(record: RecordLabel) -> Self {
+        Label::Record(record)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,6 +52,18 @@ mod tests {
         // Regular with quotes
         assert_eq!(Label::Text("a b".into()).as_string(), "\"a b\"");
 
+        // Regular with embedded quotes
+        assert_eq!(
+            Label::Text("say \"hi\"".into()).as_string(),
+            "\"say \\\"hi\\\"\""
+        );
+
+        // Graphviz escapes, like left-justified lines and the node name
+        assert_eq!(
+            Label::Text(r"left\l\N\l".into()).as_string(),
+            r#""left\l\N\l""#
+        );
+
         // HTML-like
         assert_eq!(
             Label::HtmlLike("<<bold>a</bold>>".into()).as_string(),
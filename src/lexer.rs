@@ -0,0 +1,459 @@
+use crate::ParseError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TokenKind {
+    Strict,
+    Graph,
+    Digraph,
+    Subgraph,
+    Node,
+    Edge,
+    Id(Id),
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Equals,
+    Semicolon,
+    Comma,
+    Colon,
+    Plus,
+    DirectedEdge,
+    UndirectedEdge,
+}
+
+impl TokenKind {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Strict => String::from("`strict`"),
+            Self::Graph => String::from("`graph`"),
+            Self::Digraph => String::from("`digraph`"),
+            Self::Subgraph => String::from("`subgraph`"),
+            Self::Node => String::from("`node`"),
+            Self::Edge => String::from("`edge`"),
+            Self::Id(id) => format!("identifier `{}`", id.as_str()),
+            Self::LeftBrace => String::from("`{`"),
+            Self::RightBrace => String::from("`}`"),
+            Self::LeftBracket => String::from("`[`"),
+            Self::RightBracket => String::from("`]`"),
+            Self::Equals => String::from("`=`"),
+            Self::Semicolon => String::from("`;`"),
+            Self::Comma => String::from("`,`"),
+            Self::Colon => String::from("`:`"),
+            Self::Plus => String::from("`+`"),
+            Self::DirectedEdge => String::from("`->`"),
+            Self::UndirectedEdge => String::from("`--`"),
+        }
+    }
+}
+
+/// A DOT `ID`, remembering how it was spelled in the source
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Id {
+    /// An alphanumeric identifier or a numeral
+    Plain(String),
+
+    /// A double-quoted string as Graphviz sees it, with `\"` and escaped newlines resolved
+    ///
+    /// Every other escape is kept as written, because escString attributes like `label` give
+    /// them meaning. `into_string` resolves the escapes that `sanitize` writes.
+    Quoted(String),
+
+    /// An HTML string, without its outer angle brackets
+    Html(String),
+}
+
+impl Id {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Plain(str) | Self::Quoted(str) | Self::Html(str) => str,
+        }
+    }
+
+    /// The string that `sanitize` would have written this `ID` for
+    pub fn into_string(self) -> String {
+        match self {
+            Self::Quoted(string) => unescape(&string),
+            Self::Plain(string) | Self::Html(string) => string,
+        }
+    }
+
+    /// Like `into_string`, without taking ownership
+    pub fn unescaped(&self) -> String {
+        self.clone().into_string()
+    }
+
+    /// The string with escapes kept as written, for escString attributes
+    pub fn into_esc_string(self) -> String {
+        match self {
+            Self::Plain(string) | Self::Quoted(string) | Self::Html(string) => string,
+        }
+    }
+}
+
+/// Resolve the `\\` and `\n` escapes written by `sanitize`, keeping any other escape
+fn unescape(str: &str) -> String {
+    let mut string = String::with_capacity(str.len());
+    let mut chars = str.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            string.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('\\') => string.push('\\'),
+            Some('n') => string.push('\n'),
+            Some(escaped) => {
+                string.push('\\');
+                string.push(escaped);
+            }
+            None => string.push('\\'),
+        }
+    }
+    string
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub(crate) struct Lexer {
+    chars: Vec<char>,
+    index: usize,
+    line: usize,
+    column: usize,
+    line_start: bool,
+}
+
+impl Lexer {
+    pub fn new(source: &str) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            index: 0,
+            line: 1,
+            column: 1,
+            line_start: true,
+        }
+    }
+
+    pub fn tokenize(mut self) -> Result<Vec<Token>, ParseError> {
+        let mut tokens = Vec::new();
+        while let Some(token) = self.next_token()? {
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, ParseError> {
+        self.skip_trivia()?;
+
+        let (line, column) = (self.line, self.column);
+        let c = match self.peek(0) {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+
+        let kind = match c {
+            '{' => self.single(TokenKind::LeftBrace),
+            '}' => self.single(TokenKind::RightBrace),
+            '[' => self.single(TokenKind::LeftBracket),
+            ']' => self.single(TokenKind::RightBracket),
+            '=' => self.single(TokenKind::Equals),
+            ';' => self.single(TokenKind::Semicolon),
+            ',' => self.single(TokenKind::Comma),
+            ':' => self.single(TokenKind::Colon),
+            '+' => self.single(TokenKind::Plus),
+            '-' if self.peek(1) == Some('>') => {
+                self.bump();
+                self.bump();
+                TokenKind::DirectedEdge
+            }
+            '-' if self.peek(1) == Some('-') => {
+                self.bump();
+                self.bump();
+                TokenKind::UndirectedEdge
+            }
+            '"' => TokenKind::Id(Id::Quoted(self.quoted()?)),
+            '<' => TokenKind::Id(Id::Html(self.html()?)),
+            c if c == '-' || c == '.' || c.is_ascii_digit() => {
+                TokenKind::Id(Id::Plain(self.numeral()?))
+            }
+            c if is_identifier_start(c) => self.identifier(),
+            c => return Err(self.error(line, column, format!("Unexpected character `{c}`"))),
+        };
+
+        Ok(Some(Token { kind, line, column }))
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), ParseError> {
+        while let Some(c) = self.peek(0) {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == '#' && self.line_start {
+                // Lines starting with '#' are C preprocessor output, and ignored
+                while !matches!(self.peek(0), None | Some('\n')) {
+                    self.bump();
+                }
+            } else if c == '/' && self.peek(1) == Some('/') {
+                while !matches!(self.peek(0), None | Some('\n')) {
+                    self.bump();
+                }
+            } else if c == '/' && self.peek(1) == Some('*') {
+                let (line, column) = (self.line, self.column);
+                self.bump();
+                self.bump();
+
+                loop {
+                    match self.bump() {
+                        Some('*') if self.peek(0) == Some('/') => {
+                            self.bump();
+                            break;
+                        }
+                        Some(_) => {}
+                        None => {
+                            return Err(self.error(line, column, "Unterminated comment".into()))
+                        }
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn single(&mut self, kind: TokenKind) -> TokenKind {
+        self.bump();
+        kind
+    }
+
+    fn identifier(&mut self) -> TokenKind {
+        let mut string = String::new();
+        while let Some(c) = self.peek(0) {
+            if is_identifier_start(c) || c.is_ascii_digit() {
+                string.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        match string.to_ascii_lowercase().as_str() {
+            "strict" => TokenKind::Strict,
+            "graph" => TokenKind::Graph,
+            "digraph" => TokenKind::Digraph,
+            "subgraph" => TokenKind::Subgraph,
+            "node" => TokenKind::Node,
+            "edge" => TokenKind::Edge,
+            _ => TokenKind::Id(Id::Plain(string)),
+        }
+    }
+
+    fn numeral(&mut self) -> Result<String, ParseError> {
+        let (line, column) = (self.line, self.column);
+        let mut string = String::new();
+
+        if self.peek(0) == Some('-') {
+            string.push('-');
+            self.bump();
+        }
+
+        let mut digits = false;
+        let mut dot = false;
+        while let Some(c) = self.peek(0) {
+            if c.is_ascii_digit() {
+                digits = true;
+            } else if c == '.' && !dot {
+                dot = true;
+            } else {
+                break;
+            }
+
+            string.push(c);
+            self.bump();
+        }
+
+        if digits {
+            Ok(string)
+        } else {
+            Err(self.error(line, column, format!("Invalid numeral `{string}`")))
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String, ParseError> {
+        let (line, column) = (self.line, self.column);
+        self.bump();
+
+        let mut string = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.peek(0) {
+                    Some('"') => {
+                        string.push('"');
+                        self.bump();
+                    }
+                    Some('\n') => {
+                        // An escaped newline continues the string on the next line
+                        self.bump();
+                    }
+                    // Other escapes are meaningful to Graphviz's escString, so they're kept as-is,
+                    // including `\\`, which mustn't let the following character end the string
+                    Some(c) => {
+                        string.push('\\');
+                        string.push(c);
+                        self.bump();
+                    }
+                    None => string.push('\\'),
+                },
+                Some(c) => string.push(c),
+                None => return Err(self.error(line, column, "Unterminated string".into())),
+            }
+        }
+    }
+
+    fn html(&mut self) -> Result<String, ParseError> {
+        let (line, column) = (self.line, self.column);
+        self.bump();
+
+        let mut string = String::new();
+        let mut depth = 1;
+        loop {
+            let c = match self.bump() {
+                Some(c) => c,
+                None => return Err(self.error(line, column, "Unterminated HTML string".into())),
+            };
+
+            match c {
+                '<' => depth += 1,
+                '>' if depth == 1 => return Ok(string),
+                '>' => depth -= 1,
+                _ => {}
+            }
+
+            string.push(c);
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.index += 1;
+
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+            self.line_start = true;
+        } else {
+            self.column += 1;
+            if !c.is_whitespace() {
+                self.line_start = false;
+            }
+        }
+
+        Some(c)
+    }
+
+    fn error(&self, line: usize, column: usize, message: String) -> ParseError {
+        ParseError::Syntax {
+            line,
+            column,
+            message,
+        }
+    }
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || !c.is_ascii()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        Lexer::new(source)
+            .tokenize()
+            .unwrap()
+            .into_iter()
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    #[test]
+    fn keywords() {
+        assert_eq!(
+            kinds("STRICT DiGraph subgraph node edge graph"),
+            vec![
+                TokenKind::Strict,
+                TokenKind::Digraph,
+                TokenKind::Subgraph,
+                TokenKind::Node,
+                TokenKind::Edge,
+                TokenKind::Graph,
+            ]
+        );
+    }
+
+    #[test]
+    fn ids() {
+        assert_eq!(
+            kinds(r#"a_1 -.5 3.14 "say \"hi\"" <<b>x</b>>"#),
+            vec![
+                TokenKind::Id(Id::Plain("a_1".into())),
+                TokenKind::Id(Id::Plain("-.5".into())),
+                TokenKind::Id(Id::Plain("3.14".into())),
+                TokenKind::Id(Id::Quoted("say \"hi\"".into())),
+                TokenKind::Id(Id::Html("<b>x</b>".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        let id = |source| match kinds(source).remove(0) {
+            TokenKind::Id(id) => id,
+            kind => panic!("expected an ID, found {kind:?}"),
+        };
+
+        // escString escapes are kept for labels, and resolved for IDs
+        let escaped = id(r#""a\\b\lc\nd\"e""#);
+        assert_eq!(escaped, Id::Quoted(r#"a\\b\lc\nd"e"#.into()));
+        assert_eq!(escaped.into_string(), "a\\b\\lc\nd\"e");
+
+        // An escaped backslash doesn't escape the closing quote
+        assert_eq!(id(r#""a\\""#), Id::Quoted(r"a\\".into()));
+        assert_eq!(id("\"a\\\nb\""), Id::Quoted("ab".into()));
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(
+            kinds("# preprocessor\na // line\n/* block\n */ -> b"),
+            vec![
+                TokenKind::Id(Id::Plain("a".into())),
+                TokenKind::DirectedEdge,
+                TokenKind::Id(Id::Plain("b".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn positions() {
+        let tokens = Lexer::new("a\n  -- b").tokenize().unwrap();
+        assert_eq!((tokens[1].line, tokens[1].column), (2, 3));
+
+        match Lexer::new("a\n \"open").tokenize() {
+            Err(ParseError::Syntax { line, column, .. }) => assert_eq!((line, column), (2, 2)),
+            _ => panic!("expected a syntax error"),
+        }
+    }
+}
@@ -1,36 +1,90 @@
-use std::{fs::File, io, path::Path, process::Command};
+use std::{fs::File, io, path::Path, process::Command, str::FromStr};
 use tempfile::NamedTempFile;
 use thiserror::Error;
 
+mod arrow;
+mod attributes;
 mod color;
+mod color_scheme;
 mod edge;
+mod geometry;
 mod graph;
+mod html;
 mod label;
 mod node;
+mod node_ref;
+mod polygon;
+mod record;
 
 mod compass_point;
+mod lexer;
+mod parser;
 pub(crate) mod utils;
 
-pub use color::{Color, ColorParseError};
+pub use arrow::{Arrow, ArrowError, ArrowPrimitive, ArrowShape, ArrowSide};
+pub use attributes::{Attribute, AttributeValue, Attributes};
+pub use color::{Color, ColorList, ColorParseError, WeightedColor};
+pub use color_scheme::ColorScheme;
 pub use compass_point::CompassPoint;
-pub use edge::{Edge, EdgeAttribute, EdgeStyle};
-pub use graph::Graph;
+pub use edge::{Direction, Edge, EdgeAttribute, EdgeStyle, Endpoint};
+pub use geometry::{GeometryParseError, Point, Rect, Size, Unit, Viewport};
+pub use graph::{
+    ClusterStyle, Graph, GraphAttribute, LabelJust, LabelLoc, Layout, Ordering, Overlap, Rank,
+    RankDir, Ratio, Splines, SubgraphError,
+};
+pub use html::{
+    Align, CellContent, Font, Format, HtmlError, HtmlLabel, HtmlTable, HtmlText, Image, ImageScale,
+    TableCell, TableItem, TableRow, TableWrapper, TextItem, VerticalAlign,
+};
 pub use label::Label;
-pub use node::{Node, NodeAttribute, NodeStyle, Shape};
-
+pub use node::{FixedSize, Node, NodeAttribute, NodeStyle, Shape};
+pub use node_ref::{NodeRef, Port};
+pub use parser::ParseError;
+pub use polygon::{PolygonError, PolygonShape};
+pub use record::{RecordField, RecordLabel};
+pub use utils::sanitize;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Dot {
+    pub strict: bool,
     pub directed: bool,
     pub graph: Graph,
 }
 
 impl Dot {
     pub fn new(directed: bool, graph: Graph) -> Self {
-        Self { directed, graph }
+        Self {
+            strict: false,
+            directed,
+            graph,
+        }
+    }
+
+    /// Forbid multi-edges, by emitting the `strict` keyword
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Merge duplicate edges in memory, the way Graphviz does for strict graphs
+    pub fn dedup_edges(&mut self) {
+        self.graph.dedup_edges(self.directed);
+    }
+
+    pub fn parse(mut r: impl io::Read) -> Result<Self, ParseError> {
+        let mut source = String::new();
+        r.read_to_string(&mut source)?;
+        parser::parse(&source)
     }
 
     pub fn write(&self, mut w: impl io::Write) -> io::Result<()> {
-        self.graph
-            .write(self.directed, graph::GraphType::Root, &mut w)
+        self.graph.write(
+            self.directed,
+            graph::GraphType::Root {
+                strict: self.strict,
+            },
+            &mut w,
+        )
     }
 
     pub fn write_to_string(&self) -> io::Result<String> {
@@ -74,6 +128,14 @@ impl Dot {
     }
 }
 
+impl FromStr for Dot {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse(s)
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum RenderError {
     #[error("File I/O failed")]
@@ -82,3 +144,20 @@ pub enum RenderError {
     #[error("Running `dot` failed")]
     Dot(Option<i32>),
 }
+
+/// A name that isn't one of the values an attribute accepts, such as an unknown edge style
+#[derive(Debug, Error, PartialEq)]
+#[error("Unknown {kind} `{name}`")]
+pub struct UnknownNameError {
+    pub kind: &'static str,
+    pub name: String,
+}
+
+impl UnknownNameError {
+    pub(crate) fn new(kind: &'static str, name: &str) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+        }
+    }
+}
@@ -1,42 +1,80 @@
 use crate::{
-    utils::{sanitize, write_attributes, Attribute},
-    Color, Label,
+    attributes::{Attribute, AttributeValue, Attributes},
+    lexer::Id,
+    parser::parse_bool,
+    utils::{
+        add_styles, bool_string, parse_line_width, parse_styles, sanitize, styles_string,
+        write_attributes,
+    },
+    Color, ColorList, ColorParseError, ColorScheme, ImageScale, Label, Point, PolygonError,
+    PolygonShape, Unit, UnknownNameError,
+};
+use std::{
+    convert::Infallible,
+    fmt::{self, Debug},
+    io,
+    str::FromStr,
 };
-use std::{fmt::Debug, io};
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct Node {
     pub id: String,
-    pub attributes: Vec<NodeAttribute>,
+    pub attributes: Attributes<NodeAttribute>,
 }
 
 impl Node {
     pub fn new(id: impl Into<String>) -> Self {
         Self {
             id: id.into(),
-            attributes: Vec::new(),
+            attributes: Attributes::new(),
         }
     }
 
     // --- Attributes --- //
 
+    /// Panics if `color` can't be converted, use `try_color` for colors from external input
     pub fn color<C>(self, color: C) -> Self
     where
-        C: TryInto<Color>,
+        C: TryInto<ColorList>,
         C::Error: Debug,
     {
         let color = color.try_into().unwrap();
         self.attribute(NodeAttribute::Color(color))
     }
 
+    pub fn try_color<C>(self, color: C) -> Result<Self, ColorParseError>
+    where
+        C: TryInto<ColorList>,
+        C::Error: Into<ColorParseError>,
+    {
+        let color = color.try_into().map_err(Into::into)?;
+        Ok(self.attribute(NodeAttribute::Color(color)))
+    }
+
+    pub fn color_scheme(self, scheme: ColorScheme) -> Self {
+        self.attribute(NodeAttribute::ColorScheme(scheme))
+    }
+
+    /// Panics if `color` can't be converted, use `try_fill_color` for colors from external input
     pub fn fill_color<C>(self, color: C) -> Self
     where
-        C: TryInto<Color>,
+        C: TryInto<ColorList>,
         C::Error: Debug,
     {
         let color = color.try_into().unwrap();
         self.attribute(NodeAttribute::FillColor(color))
     }
 
+    pub fn try_fill_color<C>(self, color: C) -> Result<Self, ColorParseError>
+    where
+        C: TryInto<ColorList>,
+        C::Error: Into<ColorParseError>,
+    {
+        let color = color.try_into().map_err(Into::into)?;
+        Ok(self.attribute(NodeAttribute::FillColor(color)))
+    }
+
+    /// Panics if `color` can't be converted, use `try_font_color` for colors from external input
     pub fn font_color<C>(self, color: C) -> Self
     where
         C: TryInto<Color>,
@@ -46,23 +84,185 @@ impl Node {
         self.attribute(NodeAttribute::FontColor(color))
     }
 
+    pub fn try_font_color<C>(self, color: C) -> Result<Self, ColorParseError>
+    where
+        C: TryInto<Color>,
+        C::Error: Into<ColorParseError>,
+    {
+        let color = color.try_into().map_err(Into::into)?;
+        Ok(self.attribute(NodeAttribute::FontColor(color)))
+    }
+
+    pub fn distortion(self, distortion: f32) -> Self {
+        self.attribute(NodeAttribute::Distortion(distortion))
+    }
+
+    pub fn fixed_size(self, fixed_size: FixedSize) -> Self {
+        self.attribute(NodeAttribute::FixedSize(fixed_size))
+    }
+
+    pub fn font_name(self, font_name: impl Into<String>) -> Self {
+        self.attribute(NodeAttribute::FontName(font_name.into()))
+    }
+
+    pub fn font_size(self, font_size: f32) -> Self {
+        self.attribute(NodeAttribute::FontSize(font_size))
+    }
+
+    pub fn gradient_angle(self, angle: i32) -> Self {
+        self.attribute(NodeAttribute::GradientAngle(angle))
+    }
+
+    pub fn group(self, group: impl Into<String>) -> Self {
+        self.attribute(NodeAttribute::Group(group.into()))
+    }
+
+    pub fn height(self, height: f32) -> Self {
+        self.attribute(NodeAttribute::Height(height))
+    }
+
+    pub fn href(self, href: impl Into<String>) -> Self {
+        self.attribute(NodeAttribute::Href(href.into()))
+    }
+
+    pub fn image(self, image: impl Into<String>) -> Self {
+        self.attribute(NodeAttribute::Image(image.into()))
+    }
+
+    pub fn image_scale(self, image_scale: ImageScale) -> Self {
+        self.attribute(NodeAttribute::ImageScale(image_scale))
+    }
+
     pub fn label(self, label: impl Into<Label>) -> Self {
         self.attribute(NodeAttribute::Label(label.into()))
     }
 
+    pub fn margin(self, margin: Point) -> Self {
+        self.attribute(NodeAttribute::Margin(margin))
+    }
+
+    pub fn orientation(self, degrees: f32) -> Self {
+        self.attribute(NodeAttribute::Orientation(degrees))
+    }
+
+    pub fn pen_width(self, width: f32) -> Self {
+        self.attribute(NodeAttribute::PenWidth(width))
+    }
+
+    pub fn peripheries(self, peripheries: u32) -> Self {
+        self.attribute(NodeAttribute::Peripheries(peripheries))
+    }
+
+    pub fn pin(self, pin: bool) -> Self {
+        self.attribute(NodeAttribute::Pin(pin))
+    }
+
+    /// Panics if `polygon` is invalid, use `try_polygon` for polygons from external input
+    pub fn polygon(self, polygon: PolygonShape) -> Self {
+        self.try_polygon(polygon).unwrap()
+    }
+
+    /// Set `shape=polygon` along with the polygon's sides, skew and other parameters
+    pub fn try_polygon(mut self, polygon: PolygonShape) -> Result<Self, PolygonError> {
+        polygon.validate()?;
+        self.attributes.extend(polygon.attributes());
+        Ok(self)
+    }
+
+    /// The position of the node, in points, which `neato` and `fdp` keep if it is `fixed`
+    pub fn pos(self, pos: Point) -> Self {
+        self.attribute(NodeAttribute::Pos(pos))
+    }
+
+    pub fn regular(self, regular: bool) -> Self {
+        self.attribute(NodeAttribute::Regular(regular))
+    }
+
     pub fn shape(self, shape: Option<Shape>) -> Self {
         self.attribute(NodeAttribute::Shape(shape))
     }
 
+    pub fn sides(self, sides: u32) -> Self {
+        self.attribute(NodeAttribute::Sides(sides))
+    }
+
+    pub fn skew(self, skew: f32) -> Self {
+        self.attribute(NodeAttribute::Skew(skew))
+    }
+
+    /// Add a style, combining it with any styles set before
     pub fn style(self, style: NodeStyle) -> Self {
-        self.attribute(NodeAttribute::Style(style))
+        self.styles([style])
     }
 
+    pub fn styles(mut self, styles: impl IntoIterator<Item = NodeStyle>) -> Self {
+        match self.attributes.get_mut("style") {
+            Some(NodeAttribute::Style(existing)) => add_styles(existing, styles),
+            _ => {
+                let mut new = Vec::new();
+                add_styles(&mut new, styles);
+                self.attributes.set(NodeAttribute::Style(new));
+            }
+        }
+        self
+    }
+
+    /// The `id` attribute, used to identify the node in SVG and image map output
+    pub fn svg_id(self, id: impl Into<String>) -> Self {
+        self.attribute(NodeAttribute::Id(id.into()))
+    }
+
+    pub fn target(self, target: impl Into<String>) -> Self {
+        self.attribute(NodeAttribute::Target(target.into()))
+    }
+
+    pub fn tooltip(self, tooltip: impl Into<String>) -> Self {
+        self.attribute(NodeAttribute::Tooltip(tooltip.into()))
+    }
+
+    pub fn url(self, url: impl Into<String>) -> Self {
+        self.attribute(NodeAttribute::Url(url.into()))
+    }
+
+    pub fn width(self, width: f32) -> Self {
+        self.attribute(NodeAttribute::Width(width))
+    }
+
+    pub fn xlabel(self, label: impl Into<Label>) -> Self {
+        self.attribute(NodeAttribute::XLabel(label.into()))
+    }
+
+    /// The external label position, in points
+    pub fn xlp(self, xlp: Point) -> Self {
+        self.attribute(NodeAttribute::XLp(xlp))
+    }
+
+    /// Set an attribute, replacing any earlier attribute with the same key
     pub fn attribute(mut self, attribute: NodeAttribute) -> Self {
-        self.attributes.push(attribute);
+        self.attributes.set(attribute);
         self
     }
 
+    /// Set an attribute of a user-defined type, see `Attribute` for how to format its value
+    pub fn attr(self, attribute: impl Attribute) -> Self {
+        let (key, value) = attribute.pair();
+        let attribute = NodeAttribute::Unknown(key.to_string(), AttributeValue::Raw(value));
+        self.attribute(attribute)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&NodeAttribute> {
+        self.attributes.get(key)
+    }
+
+    /// Set an attribute after construction, returning the one it replaced, if any
+    pub fn set(&mut self, attribute: NodeAttribute) -> Option<NodeAttribute> {
+        self.attributes.set(attribute)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<NodeAttribute> {
+        self.attributes.remove(key)
+    }
+
     pub(crate) fn write(&self, mut w: impl io::Write) -> io::Result<()> {
         write!(w, "{}", sanitize(&self.id))?;
 
@@ -74,23 +274,117 @@ impl Node {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum NodeAttribute {
-    Color(Color),
-    FillColor(Color),
+    Color(ColorList),
+    ColorScheme(ColorScheme),
+    Distortion(f32),
+    FillColor(ColorList),
+    FixedSize(FixedSize),
     FontColor(Color),
+    FontName(String),
+    FontSize(f32),
+    GradientAngle(i32),
+    Group(String),
+    Height(f32),
+    Href(String),
+    Id(String),
+    Image(String),
+    ImageScale(ImageScale),
     Label(Label),
+    Margin(Point),
+    Orientation(f32),
+    PenWidth(f32),
+    Peripheries(u32),
+    Pin(bool),
+    Pos(Point),
+    Regular(bool),
     Shape(Option<Shape>),
-    Style(NodeStyle),
-    Unknown(String, String),
+    Sides(u32),
+    Skew(f32),
+    Style(Vec<NodeStyle>),
+    Target(String),
+    Tooltip(String),
+    Url(String),
+    Width(f32),
+    XLabel(Label),
+    XLp(Point),
+    Unknown(String, AttributeValue),
+}
+
+impl NodeAttribute {
+    pub(crate) fn from_pair(key: &str, value: Id) -> Self {
+        let str = value.as_str();
+
+        let attribute = match key {
+            "color" => ColorList::from_id(&value).map(Self::Color),
+            "colorscheme" => str.parse().ok().map(Self::ColorScheme),
+            "distortion" => str.parse().ok().map(Self::Distortion),
+            "fillcolor" => ColorList::from_id(&value).map(Self::FillColor),
+            "fixedsize" => FixedSize::from_name(str).map(Self::FixedSize),
+            "fontcolor" => return Self::FontColor(Color::from_id(value)),
+            "fontname" => Some(Self::FontName(value.unescaped())),
+            "fontsize" => str.parse().ok().map(Self::FontSize),
+            "gradientangle" => str.parse().ok().map(Self::GradientAngle),
+            "group" => Some(Self::Group(value.unescaped())),
+            "height" => str.parse().ok().map(Self::Height),
+            "href" => Some(Self::Href(value.unescaped())),
+            "id" => Some(Self::Id(value.unescaped())),
+            "image" => Some(Self::Image(value.unescaped())),
+            "imagescale" => ImageScale::from_name(str).map(Self::ImageScale),
+            "label" => return Self::Label(Label::from_id(value)),
+            "margin" => Point::parse(str).map(Self::Margin),
+            "orientation" => str.parse().ok().map(Self::Orientation),
+            "penwidth" => str.parse().ok().map(Self::PenWidth),
+            "peripheries" => str.parse().ok().map(Self::Peripheries),
+            "pin" => parse_bool(str).map(Self::Pin),
+            "pos" => Point::parse(str).map(Self::Pos),
+            "regular" => parse_bool(str).map(Self::Regular),
+            "shape" => Some(Self::Shape(Shape::from_name(str))),
+            "sides" => str.parse().ok().map(Self::Sides),
+            "skew" => str.parse().ok().map(Self::Skew),
+            "style" => parse_styles(str, NodeStyle::from_name).map(Self::Style),
+            "target" => Some(Self::Target(value.unescaped())),
+            "tooltip" => Some(Self::Tooltip(value.unescaped())),
+            "URL" => Some(Self::Url(value.unescaped())),
+            "width" => str.parse().ok().map(Self::Width),
+            "xlabel" => return Self::XLabel(Label::from_id(value)),
+            "xlp" => Point::parse(str).map(Self::XLp),
+            _ => None,
+        };
+
+        attribute.unwrap_or_else(|| Self::Unknown(key.to_string(), AttributeValue::from_id(value)))
+    }
 }
 
 impl Attribute for NodeAttribute {
     fn pair(&self) -> (&str, String) {
         match self {
             Self::Color(color) => ("color", sanitize(&color.as_string())),
+            Self::ColorScheme(scheme) => ("colorscheme", scheme.as_string()),
+            Self::Distortion(distortion) => ("distortion", format!("{distortion}")),
             Self::FillColor(color) => ("fillcolor", sanitize(&color.as_string())),
+            Self::FixedSize(fixed_size) => ("fixedsize", fixed_size.as_str().to_string()),
             Self::FontColor(color) => ("fontcolor", sanitize(&color.as_string())),
+            Self::FontName(font_name) => ("fontname", sanitize(font_name)),
+            Self::FontSize(font_size) => ("fontsize", format!("{font_size}")),
+            Self::GradientAngle(angle) => ("gradientangle", format!("{angle}")),
+            Self::Group(group) => ("group", sanitize(group)),
+            Self::Height(height) => ("height", format!("{height}")),
+            Self::Href(href) => ("href", sanitize(href)),
+            Self::Id(id) => ("id", sanitize(id)),
+            Self::Image(image) => ("image", sanitize(image)),
+            Self::ImageScale(image_scale) => {
+                ("imagescale", image_scale.as_str().to_ascii_lowercase())
+            }
             Self::Label(label) => ("label", label.as_string()),
+            Self::Margin(margin) => ("margin", sanitize(&margin.as_string_in(Unit::Inches))),
+            Self::Orientation(degrees) => ("orientation", format!("{degrees}")),
+            Self::PenWidth(width) => ("penwidth", format!("{width}")),
+            Self::Peripheries(peripheries) => ("peripheries", format!("{peripheries}")),
+            Self::Pin(pin) => ("pin", bool_string(*pin)),
+            Self::Pos(pos) => ("pos", sanitize(&pos.as_string_in(Unit::Points))),
+            Self::Regular(regular) => ("regular", bool_string(*regular)),
             Self::Shape(shape) => (
                 "shape",
                 match shape {
@@ -98,66 +392,316 @@ impl Attribute for NodeAttribute {
                     None => String::from("none"),
                 },
             ),
-            Self::Style(style) => ("style", style.as_str().to_string()),
-            Self::Unknown(key, value) => (key, sanitize(value)),
+            Self::Sides(sides) => ("sides", format!("{sides}")),
+            Self::Skew(skew) => ("skew", format!("{skew}")),
+            Self::Style(styles) => ("style", styles_string(styles, NodeStyle::as_string)),
+            Self::Target(target) => ("target", sanitize(target)),
+            Self::Tooltip(tooltip) => ("tooltip", sanitize(tooltip)),
+            Self::Url(url) => ("URL", sanitize(url)),
+            Self::Width(width) => ("width", format!("{width}")),
+            Self::XLabel(label) => ("xlabel", label.as_string()),
+            Self::XLp(xlp) => ("xlp", sanitize(&xlp.as_string_in(Unit::Points))),
+            Self::Unknown(key, value) => (key, value.as_string()),
+        }
+    }
+
+    fn key(&self) -> &str {
+        match self {
+            Self::Color(_) => "color",
+            Self::ColorScheme(_) => "colorscheme",
+            Self::Distortion(_) => "distortion",
+            Self::FillColor(_) => "fillcolor",
+            Self::FixedSize(_) => "fixedsize",
+            Self::FontColor(_) => "fontcolor",
+            Self::FontName(_) => "fontname",
+            Self::FontSize(_) => "fontsize",
+            Self::GradientAngle(_) => "gradientangle",
+            Self::Group(_) => "group",
+            Self::Height(_) => "height",
+            Self::Href(_) => "href",
+            Self::Id(_) => "id",
+            Self::Image(_) => "image",
+            Self::ImageScale(_) => "imagescale",
+            Self::Label(_) => "label",
+            Self::Margin(_) => "margin",
+            Self::Orientation(_) => "orientation",
+            Self::PenWidth(_) => "penwidth",
+            Self::Peripheries(_) => "peripheries",
+            Self::Pin(_) => "pin",
+            Self::Pos(_) => "pos",
+            Self::Regular(_) => "regular",
+            Self::Shape(_) => "shape",
+            Self::Sides(_) => "sides",
+            Self::Skew(_) => "skew",
+            Self::Style(_) => "style",
+            Self::Target(_) => "target",
+            Self::Tooltip(_) => "tooltip",
+            Self::Url(_) => "URL",
+            Self::Width(_) => "width",
+            Self::XLabel(_) => "xlabel",
+            Self::XLp(_) => "xlp",
+            Self::Unknown(key, _) => key,
+        }
+    }
+}
+
+/// Whether a node's size is determined by its `width` and `height` alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixedSize {
+    False,
+    True,
+
+    /// Use the fixed size for the shape, but let the label determine the space it takes in layout
+    Shape,
+}
+
+impl FixedSize {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::False => "false",
+            Self::True => "true",
+            Self::Shape => "shape",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "shape" => Some(Self::Shape),
+            name => parse_bool(name).map(|fixed| if fixed { Self::True } else { Self::False }),
         }
     }
 }
 
+/// Every node shape Graphviz knows, with any other name kept as `Unknown`
+///
+/// Graphviz treats `ellipse` and `oval`, and `box`, `rect` and `rectangle`, as the same shape, but
+/// they are kept apart here so that a parsed shape is written back the way it was given.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Shape {
+    Assembly,
     Box,
+    Box3d,
+    Cds,
     Circle,
+    Component,
+    Cylinder,
     Diamond,
+    DoubleCircle,
+    DoubleOctagon,
     Egg,
-    House,
+    Ellipse,
+    FivePOverhang,
+    Folder,
     Hexagon,
+    House,
+    Insulator,
     InvHouse,
     InvTrapezium,
     InvTriangle,
+    LArrow,
+    LPromoter,
+    MCircle,
+    MDiamond,
+    MRecord,
+    MSquare,
     Note,
+    NOverhang,
     Octagon,
+    Oval,
     Parallelogram,
     Pentagon,
     Plain,
+    PlainText,
+    Point,
     Polygon,
+    PrimerSite,
+    Promoter,
+    ProteaseSite,
+    ProteinStab,
+    RArrow,
+    Record,
+    Rect,
+    Rectangle,
+    RestrictionSite,
+    RiboSite,
+    RnaStab,
+    RPromoter,
     Septagon,
+    Signature,
     Square,
     Star,
+    Tab,
+    Terminator,
+    ThreePOverhang,
     Trapezium,
     Triangle,
+    TripleOctagon,
     Underline,
+    Utr,
     Unknown(String),
 }
 
 impl Shape {
+    /// Parse a shape name, where `none` means no shape at all
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => None,
+            name => Some(Self::from_shape_name(name)),
+        }
+    }
+
+    fn from_shape_name(name: &str) -> Self {
+        match name {
+            "assembly" => Self::Assembly,
+            "box" => Self::Box,
+            "box3d" => Self::Box3d,
+            "cds" => Self::Cds,
+            "circle" => Self::Circle,
+            "component" => Self::Component,
+            "cylinder" => Self::Cylinder,
+            "diamond" => Self::Diamond,
+            "doublecircle" => Self::DoubleCircle,
+            "doubleoctagon" => Self::DoubleOctagon,
+            "egg" => Self::Egg,
+            "ellipse" => Self::Ellipse,
+            "fivepoverhang" => Self::FivePOverhang,
+            "folder" => Self::Folder,
+            "hexagon" => Self::Hexagon,
+            "house" => Self::House,
+            "insulator" => Self::Insulator,
+            "invhouse" => Self::InvHouse,
+            "invtrapezium" => Self::InvTrapezium,
+            "invtriangle" => Self::InvTriangle,
+            "larrow" => Self::LArrow,
+            "lpromoter" => Self::LPromoter,
+            "Mcircle" => Self::MCircle,
+            "Mdiamond" => Self::MDiamond,
+            "Mrecord" => Self::MRecord,
+            "Msquare" => Self::MSquare,
+            "note" => Self::Note,
+            "noverhang" => Self::NOverhang,
+            "octagon" => Self::Octagon,
+            "oval" => Self::Oval,
+            "parallelogram" => Self::Parallelogram,
+            "pentagon" => Self::Pentagon,
+            "plain" => Self::Plain,
+            "plaintext" => Self::PlainText,
+            "point" => Self::Point,
+            "polygon" => Self::Polygon,
+            "primersite" => Self::PrimerSite,
+            "promoter" => Self::Promoter,
+            "proteasesite" => Self::ProteaseSite,
+            "proteinstab" => Self::ProteinStab,
+            "rarrow" => Self::RArrow,
+            "record" => Self::Record,
+            "rect" => Self::Rect,
+            "rectangle" => Self::Rectangle,
+            "restrictionsite" => Self::RestrictionSite,
+            "ribosite" => Self::RiboSite,
+            "rnastab" => Self::RnaStab,
+            "rpromoter" => Self::RPromoter,
+            "septagon" => Self::Septagon,
+            "signature" => Self::Signature,
+            "square" => Self::Square,
+            "star" => Self::Star,
+            "tab" => Self::Tab,
+            "terminator" => Self::Terminator,
+            "threepoverhang" => Self::ThreePOverhang,
+            "trapezium" => Self::Trapezium,
+            "triangle" => Self::Triangle,
+            "tripleoctagon" => Self::TripleOctagon,
+            "underline" => Self::Underline,
+            "utr" => Self::Utr,
+            name => Self::Unknown(name.to_string()),
+        }
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
+            Self::Assembly => "assembly",
             Self::Box => "box",
+            Self::Box3d => "box3d",
+            Self::Cds => "cds",
             Self::Circle => "circle",
+            Self::Component => "component",
+            Self::Cylinder => "cylinder",
             Self::Diamond => "diamond",
+            Self::DoubleCircle => "doublecircle",
+            Self::DoubleOctagon => "doubleoctagon",
             Self::Egg => "egg",
+            Self::Ellipse => "ellipse",
+            Self::FivePOverhang => "fivepoverhang",
+            Self::Folder => "folder",
             Self::Hexagon => "hexagon",
             Self::House => "house",
+            Self::Insulator => "insulator",
             Self::InvHouse => "invhouse",
             Self::InvTrapezium => "invtrapezium",
             Self::InvTriangle => "invtriangle",
+            Self::LArrow => "larrow",
+            Self::LPromoter => "lpromoter",
+            Self::MCircle => "Mcircle",
+            Self::MDiamond => "Mdiamond",
+            Self::MRecord => "Mrecord",
+            Self::MSquare => "Msquare",
             Self::Note => "note",
+            Self::NOverhang => "noverhang",
             Self::Octagon => "octagon",
-            Self::Pentagon => "pentagon",
+            Self::Oval => "oval",
             Self::Parallelogram => "parallelogram",
+            Self::Pentagon => "pentagon",
             Self::Plain => "plain",
+            Self::PlainText => "plaintext",
+            Self::Point => "point",
             Self::Polygon => "polygon",
+            Self::PrimerSite => "primersite",
+            Self::Promoter => "promoter",
+            Self::ProteaseSite => "proteasesite",
+            Self::ProteinStab => "proteinstab",
+            Self::RArrow => "rarrow",
+            Self::Record => "record",
+            Self::Rect => "rect",
+            Self::Rectangle => "rectangle",
+            Self::RestrictionSite => "restrictionsite",
+            Self::RiboSite => "ribosite",
+            Self::RnaStab => "rnastab",
+            Self::RPromoter => "rpromoter",
             Self::Septagon => "septagon",
+            Self::Signature => "signature",
             Self::Square => "square",
             Self::Star => "star",
+            Self::Tab => "tab",
+            Self::Terminator => "terminator",
+            Self::ThreePOverhang => "threepoverhang",
             Self::Trapezium => "trapezium",
             Self::Triangle => "triangle",
+            Self::TripleOctagon => "tripleoctagon",
             Self::Underline => "underline",
+            Self::Utr => "utr",
             Self::Unknown(str) => str,
         }
     }
 }
 
+/// Parsing never fails, as unknown names become `Shape::Unknown`
+///
+/// This includes `none`, which `Node::shape` spells as `None` instead.
+impl FromStr for Shape {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_shape_name(s))
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A node style, several of which can be combined through `Node::styles`
+#[derive(Debug, Clone, PartialEq)]
 pub enum NodeStyle {
     Bold,
     Dashed,
@@ -165,14 +709,48 @@ pub enum NodeStyle {
     Dotted,
     Filled,
     Invisible,
+    Radial,
     Rounded,
     Solid,
     Striped,
     Wedged,
+
+    /// Deprecated by Graphviz in favor of `penwidth`, but still understood
+    SetLineWidth(f32),
 }
 
 impl NodeStyle {
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "bold" => Some(Self::Bold),
+            "dashed" => Some(Self::Dashed),
+            "diagonals" => Some(Self::Diagonals),
+            "dotted" => Some(Self::Dotted),
+            "filled" => Some(Self::Filled),
+            "invis" => Some(Self::Invisible),
+            "radial" => Some(Self::Radial),
+            "rounded" => Some(Self::Rounded),
+            "solid" => Some(Self::Solid),
+            "striped" => Some(Self::Striped),
+            "wedged" => Some(Self::Wedged),
+            _ => parse_line_width(name).map(Self::SetLineWidth),
+        }
+    }
+
+    /// The style's name, without the width of `setlinewidth`
+    #[deprecated(note = "use `as_string`, which includes the width of `setlinewidth`")]
     pub fn as_str(&self) -> &'static str {
+        self.name()
+    }
+
+    pub fn as_string(&self) -> String {
+        match self {
+            Self::SetLineWidth(width) => format!("{}({width})", self.name()),
+            style => style.name().to_string(),
+        }
+    }
+
+    fn name(&self) -> &'static str {
         match self {
             Self::Bold => "bold",
             Self::Dashed => "dashed",
@@ -180,10 +758,26 @@ impl NodeStyle {
             Self::Dotted => "dotted",
             Self::Filled => "filled",
             Self::Invisible => "invis",
+            Self::Radial => "radial",
             Self::Rounded => "rounded",
             Self::Solid => "solid",
             Self::Striped => "striped",
             Self::Wedged => "wedged",
+            Self::SetLineWidth(_) => "setlinewidth",
         }
     }
 }
+
+impl FromStr for NodeStyle {
+    type Err = UnknownNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s).ok_or_else(|| UnknownNameError::new("node style", s))
+    }
+}
+
+impl fmt::Display for NodeStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.as_string())
+    }
+}
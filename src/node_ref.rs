@@ -0,0 +1,173 @@
+use crate::{utils::sanitize, CompassPoint};
+use std::io;
+
+/// A reference to a node in an edge, optionally to one of its ports, like `a:f0:ne`
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeRef {
+    pub id: String,
+    pub port: Option<String>,
+    pub compass: Option<CompassPoint>,
+}
+
+impl NodeRef {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            port: None,
+            compass: None,
+        }
+    }
+
+    /// Attach to a named port, like a record field or an HTML table cell
+    pub fn port(mut self, port: impl Into<String>) -> Self {
+        self.port = Some(port.into());
+        self
+    }
+
+    pub fn compass(mut self, compass: CompassPoint) -> Self {
+        self.compass = Some(compass);
+        self
+    }
+
+    pub(crate) fn write(&self, mut w: impl io::Write) -> io::Result<()> {
+        write!(w, "{}", sanitize(&self.id))?;
+
+        if let Some(port) = &self.port {
+            write!(w, ":{}", sanitize(port))?;
+        }
+
+        if let Some(compass) = &self.compass {
+            write!(w, ":{}", compass.as_str())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Where on a node an edge attaches, written as `port`, `compass` or `port:compass`
+///
+/// This is the value of the `headport` and `tailport` attributes, which do the same as the port
+/// of a `NodeRef`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Port {
+    pub name: Option<String>,
+    pub compass: Option<CompassPoint>,
+}
+
+impl Port {
+    /// A named port, like a record field or an HTML table cell
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+            compass: None,
+        }
+    }
+
+    pub fn compass(mut self, compass: CompassPoint) -> Self {
+        self.compass = Some(compass);
+        self
+    }
+
+    pub fn as_string(&self) -> String {
+        match (&self.name, &self.compass) {
+            (Some(name), Some(compass)) => format!("{name}:{}", compass.as_str()),
+            (Some(name), None) => name.clone(),
+            (None, Some(compass)) => compass.as_str().to_string(),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// Parse a port, where a single name is a compass point unless it can't be
+    pub(crate) fn parse(str: &str) -> Self {
+        if let Some((name, compass)) = str.rsplit_once(':') {
+            if let Some(compass) = CompassPoint::from_name(compass) {
+                return Self::new(name).compass(compass);
+            }
+        }
+
+        match CompassPoint::from_name(str) {
+            Some(compass) => compass.into(),
+            None => Self::new(str),
+        }
+    }
+}
+
+impl From<CompassPoint> for Port {
+    fn from(compass: CompassPoint) -> Self {
+        Self {
+            name: None,
+            compass: Some(compass),
+        }
+    }
+}
+
+impl From<&str> for Port {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for Port {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<&str> for NodeRef {
+    fn from(id: &str) -> Self {
+        Self::new(id)
+    }
+}
+
+impl From<String> for NodeRef {
+    fn from(id: String) -> Self {
+        Self::new(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn written(node: NodeRef) -> String {
+        let mut vec = Vec::new();
+        node.write(&mut vec).unwrap();
+        String::from_utf8(vec).unwrap()
+    }
+
+    #[test]
+    fn write() {
+        assert_eq!(written("a:f0".into()), "\"a:f0\"");
+        assert_eq!(written(NodeRef::new("a").port("f0")), "a:f0");
+        assert_eq!(
+            written(NodeRef::new("a").compass(CompassPoint::South)),
+            "a:s"
+        );
+        assert_eq!(
+            written(
+                NodeRef::new("a")
+                    .port("f 0")
+                    .compass(CompassPoint::NorthEast)
+            ),
+            "a:\"f 0\":ne"
+        );
+    }
+
+    #[test]
+    fn ports() {
+        assert_eq!(Port::new("f0").as_string(), "f0");
+        assert_eq!(Port::from(CompassPoint::South).as_string(), "s");
+        assert_eq!(
+            Port::new("f0").compass(CompassPoint::NorthEast).as_string(),
+            "f0:ne"
+        );
+
+        assert_eq!(
+            Port::parse("f0:ne"),
+            Port::new("f0").compass(CompassPoint::NorthEast)
+        );
+        assert_eq!(Port::parse("w"), Port::from(CompassPoint::West));
+        assert_eq!(Port::parse("f0"), Port::new("f0"));
+        assert_eq!(Port::parse("a:b"), Port::new("a:b"));
+    }
+}
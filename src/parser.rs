@@ -0,0 +1,620 @@
+use crate::{
+    lexer::{Id, Lexer, Token, TokenKind},
+    Attributes, CompassPoint, Dot, Edge, EdgeAttribute, Endpoint, Graph, GraphAttribute, Node,
+    NodeAttribute, NodeRef,
+};
+use std::{collections::HashSet, io};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Reading the input failed")]
+    IO(#[from] io::Error),
+
+    #[error("{message} at line {line}, column {column}")]
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+}
+
+pub(crate) fn parse(source: &str) -> Result<Dot, ParseError> {
+    let tokens = Lexer::new(source).tokenize()?;
+    Parser {
+        tokens,
+        index: 0,
+        directed: false,
+        nested: false,
+        declared: HashSet::new(),
+    }
+    .dot()
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    directed: bool,
+
+    /// Whether the statements being parsed are inside a subgraph
+    nested: bool,
+
+    /// Every node mentioned so far, as defaults only apply to nodes when they are created
+    declared: HashSet<String>,
+}
+
+/// Defaults that couldn't be written as default statements without changing what they apply to
+///
+/// `Graph` writes default statements at the top, so a default that follows other statements is
+/// applied to the nodes, edges and subgraphs created after it instead.
+#[derive(Clone, Default)]
+struct Scope {
+    graph_defaults: Attributes<GraphAttribute>,
+    node_defaults: Attributes<NodeAttribute>,
+    edge_defaults: Attributes<EdgeAttribute>,
+}
+
+impl Parser {
+    fn dot(&mut self) -> Result<Dot, ParseError> {
+        let strict = self.eat(&TokenKind::Strict);
+
+        let token = self.next("`graph` or `digraph`")?;
+        self.directed = match token.kind {
+            TokenKind::Graph => false,
+            TokenKind::Digraph => true,
+            _ => return Err(unexpected(&token, "`graph` or `digraph`")),
+        };
+
+        let id = self.optional_id()?;
+        self.expect(&TokenKind::LeftBrace)?;
+
+        let mut graph = Graph::new(id);
+        self.statements(&mut graph, &mut Scope::default())?;
+
+        if let Some(token) = self.tokens.get(self.index) {
+            return Err(unexpected(token, "end of input"));
+        }
+
+        let dot = Dot::new(self.directed, graph);
+        Ok(if strict { dot.strict() } else { dot })
+    }
+
+    /// Parse statements up to and including the closing brace
+    fn statements(&mut self, graph: &mut Graph, scope: &mut Scope) -> Result<(), ParseError> {
+        while !self.eat(&TokenKind::RightBrace) {
+            self.statement(graph, scope)?;
+            self.eat(&TokenKind::Semicolon);
+        }
+
+        Ok(())
+    }
+
+    /// Parse a single statement into `graph`
+    ///
+    /// Default statements are kept as defaults while nothing they could apply to comes before
+    /// them in their (sub)graph. Later ones go into `scope`, to keep them from applying to what
+    /// was created before them. Node defaults in subgraphs always go into `scope`: subgraphs are
+    /// written before the nodes of their parent, so a node mentioned in a subgraph after being
+    /// created elsewhere would otherwise pick them up.
+    fn statement(&mut self, graph: &mut Graph, scope: &mut Scope) -> Result<(), ParseError> {
+        let token = match self.tokens.get(self.index) {
+            Some(token) => token.clone(),
+            None => return Err(self.end_of_input("`}`")),
+        };
+
+        match &token.kind {
+            TokenKind::Graph => {
+                self.index += 1;
+                let hoist = graph.subgraphs.is_empty() && graph.edges.is_empty();
+                for (key, value) in self.attribute_lists()? {
+                    let attribute = GraphAttribute::from_pair(&key, value);
+                    if hoist {
+                        scope.graph_defaults.remove(&key);
+                        graph.graph_defaults.set(attribute);
+                    } else {
+                        scope.graph_defaults.set(attribute.clone());
+                        graph.attributes.set(attribute);
+                    }
+                }
+            }
+            TokenKind::Node => {
+                self.index += 1;
+                let hoist = !self.nested
+                    && graph.nodes.is_empty()
+                    && graph.edges.is_empty()
+                    && graph.subgraphs.is_empty();
+                for (key, value) in self.attribute_lists()? {
+                    let attribute = NodeAttribute::from_pair(&key, value);
+                    if hoist {
+                        scope.node_defaults.remove(&key);
+                        graph.node_defaults.set(attribute);
+                    } else {
+                        scope.node_defaults.set(attribute);
+                    }
+                }
+            }
+            TokenKind::Edge => {
+                self.index += 1;
+                let hoist = graph.edges.is_empty() && graph.subgraphs.is_empty();
+                for (key, value) in self.attribute_lists()? {
+                    let attribute = EdgeAttribute::from_pair(&key, value);
+                    if hoist {
+                        scope.edge_defaults.remove(&key);
+                        graph.edge_defaults.set(attribute);
+                    } else {
+                        scope.edge_defaults.set(attribute);
+                    }
+                }
+            }
+            TokenKind::Subgraph | TokenKind::LeftBrace => {
+                let subgraph = self.subgraph(scope)?;
+                if self.at_edge_operator() {
+                    self.edges(Endpoint::Subgraph(subgraph), graph, scope)?;
+                } else {
+                    graph.subgraphs.push(subgraph);
+                }
+            }
+            TokenKind::Id(_)
+                if self.tokens.get(self.index + 1).map(|token| &token.kind)
+                    == Some(&TokenKind::Equals) =>
+            {
+                let key = self.id()?.into_string();
+                self.index += 1;
+                let value = self.id()?;
+                graph.attributes.set(GraphAttribute::from_pair(&key, value));
+            }
+            TokenKind::Id(_) => {
+                let node_ref = self.node_ref()?;
+                if self.at_edge_operator() {
+                    self.edges(Endpoint::Node(node_ref), graph, scope)?;
+                } else {
+                    // Ports on node statements are meaningless, and ignored like Graphviz does
+                    let mut node = Node::new(node_ref.id);
+                    if self.declared.insert(node.id.clone()) {
+                        node.attributes.extend(scope.node_defaults.iter().cloned());
+                    }
+                    for (key, value) in self.optional_attribute_lists()? {
+                        node.attributes.set(NodeAttribute::from_pair(&key, value));
+                    }
+
+                    graph.nodes.push(node);
+                }
+            }
+            _ => return Err(unexpected(&token, "a statement")),
+        }
+
+        Ok(())
+    }
+
+    fn subgraph(&mut self, scope: &Scope) -> Result<Graph, ParseError> {
+        let id = if self.eat(&TokenKind::Subgraph) {
+            self.optional_id()?
+        } else {
+            None
+        };
+
+        let mut subgraph = match id {
+            Some(id) if id == "cluster" => Graph::new(None).cluster(),
+            Some(id) => match id.strip_prefix("cluster_") {
+                Some(name) => Graph::new(Some(name.to_string())).cluster(),
+                // Graphviz takes any name starting with `cluster` as a cluster
+                None if id.starts_with("cluster") => Graph::new(Some(id)).cluster(),
+                None => Graph::new(Some(id)),
+            },
+            None => Graph::new(None),
+        };
+
+        // Subgraphs inherit the graph defaults that are current when they are created
+        subgraph
+            .attributes
+            .extend(scope.graph_defaults.iter().cloned());
+
+        self.expect(&TokenKind::LeftBrace)?;
+        let nested = std::mem::replace(&mut self.nested, true);
+        self.statements(&mut subgraph, &mut scope.clone())?;
+        self.nested = nested;
+
+        Ok(subgraph)
+    }
+
+    /// Parse the rest of an edge statement, after its first endpoint
+    fn edges(
+        &mut self,
+        first: Endpoint,
+        graph: &mut Graph,
+        scope: &mut Scope,
+    ) -> Result<(), ParseError> {
+        let mut endpoints = vec![first];
+        while self.at_edge_operator() {
+            let token = self.tokens[self.index].clone();
+            let expected = if self.directed {
+                TokenKind::DirectedEdge
+            } else {
+                TokenKind::UndirectedEdge
+            };
+
+            if token.kind != expected {
+                return Err(ParseError::Syntax {
+                    line: token.line,
+                    column: token.column,
+                    message: format!(
+                        "Found {} in {} graph",
+                        token.kind.describe(),
+                        if self.directed {
+                            "a directed"
+                        } else {
+                            "an undirected"
+                        }
+                    ),
+                });
+            }
+
+            self.index += 1;
+            match self.peek_kind() {
+                Some(TokenKind::Subgraph | TokenKind::LeftBrace) => {
+                    endpoints.push(Endpoint::Subgraph(self.subgraph(scope)?));
+                }
+                _ => endpoints.push(Endpoint::Node(self.node_ref()?)),
+            }
+        }
+
+        // Nodes that are first mentioned in an edge still pick up the node defaults
+        for endpoint in &endpoints {
+            if let Endpoint::Node(node_ref) = endpoint {
+                let created = self.declared.insert(node_ref.id.clone());
+                if created && !scope.node_defaults.is_empty() {
+                    let mut node = Node::new(node_ref.id.clone());
+                    node.attributes.extend(scope.node_defaults.iter().cloned());
+                    graph.nodes.push(node);
+                }
+            }
+        }
+
+        let mut edge = Edge::chain(endpoints);
+        edge.attributes.extend(scope.edge_defaults.iter().cloned());
+        for (key, value) in self.optional_attribute_lists()? {
+            edge.attributes.set(EdgeAttribute::from_pair(&key, value));
+        }
+
+        graph.edges.push(edge);
+        Ok(())
+    }
+
+    fn node_ref(&mut self) -> Result<NodeRef, ParseError> {
+        let node_ref = NodeRef::new(self.id()?.into_string());
+        if !self.eat(&TokenKind::Colon) {
+            return Ok(node_ref);
+        }
+
+        let first = self.id()?.into_string();
+        if self.eat(&TokenKind::Colon) {
+            let index = self.index;
+            let second = self.id()?.into_string();
+            match CompassPoint::from_name(&second) {
+                Some(compass) => Ok(node_ref.port(first).compass(compass)),
+                None => {
+                    let token = &self.tokens[index];
+                    Err(ParseError::Syntax {
+                        line: token.line,
+                        column: token.column,
+                        message: format!("Invalid compass point `{second}`"),
+                    })
+                }
+            }
+        } else {
+            // A single name is a compass point, unless it can't be
+            match CompassPoint::from_name(&first) {
+                Some(compass) => Ok(node_ref.compass(compass)),
+                None => Ok(node_ref.port(first)),
+            }
+        }
+    }
+
+    fn attribute_lists(&mut self) -> Result<Vec<(String, Id)>, ParseError> {
+        match self.tokens.get(self.index) {
+            Some(token) if token.kind == TokenKind::LeftBracket => {}
+            Some(token) => return Err(unexpected(token, "`[`")),
+            None => return Err(self.end_of_input("`[`")),
+        }
+
+        self.optional_attribute_lists()
+    }
+
+    fn optional_attribute_lists(&mut self) -> Result<Vec<(String, Id)>, ParseError> {
+        let mut pairs = Vec::new();
+        while self.eat(&TokenKind::LeftBracket) {
+            while !self.eat(&TokenKind::RightBracket) {
+                let key = self.id()?.into_string();
+                self.expect(&TokenKind::Equals)?;
+                let value = self.id()?;
+                pairs.push((key, value));
+
+                if !self.eat(&TokenKind::Comma) {
+                    self.eat(&TokenKind::Semicolon);
+                }
+            }
+        }
+
+        Ok(pairs)
+    }
+
+    fn optional_id(&mut self) -> Result<Option<String>, ParseError> {
+        match self.peek_kind() {
+            Some(TokenKind::Id(_)) => Ok(Some(self.id()?.into_string())),
+            _ => Ok(None),
+        }
+    }
+
+    /// Parse an ID, including quoted strings concatenated with `+`
+    fn id(&mut self) -> Result<Id, ParseError> {
+        let token = self.next("an identifier")?;
+        let mut string = match token.kind {
+            TokenKind::Id(Id::Quoted(string)) => string,
+            TokenKind::Id(id) => return Ok(id),
+            _ => return Err(unexpected(&token, "an identifier")),
+        };
+
+        while self.eat(&TokenKind::Plus) {
+            let token = self.next("a quoted string")?;
+            match token.kind {
+                TokenKind::Id(Id::Quoted(next)) => string.push_str(&next),
+                _ => return Err(unexpected(&token, "a quoted string")),
+            }
+        }
+
+        Ok(Id::Quoted(string))
+    }
+
+    fn at_edge_operator(&self) -> bool {
+        matches!(
+            self.peek_kind(),
+            Some(TokenKind::DirectedEdge | TokenKind::UndirectedEdge)
+        )
+    }
+
+    fn peek_kind(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.index).map(|token| &token.kind)
+    }
+
+    fn next(&mut self, expected: &str) -> Result<Token, ParseError> {
+        match self.tokens.get(self.index) {
+            Some(token) => {
+                self.index += 1;
+                Ok(token.clone())
+            }
+            None => Err(self.end_of_input(expected)),
+        }
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        if self.peek_kind() == Some(kind) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, kind: &TokenKind) -> Result<(), ParseError> {
+        let expected = kind.describe();
+        let token = self.next(&expected)?;
+        if &token.kind == kind {
+            Ok(())
+        } else {
+            Err(unexpected(&token, &expected))
+        }
+    }
+
+    fn end_of_input(&self, expected: &str) -> ParseError {
+        let (line, column) = match self.tokens.last() {
+            Some(token) => (token.line, token.column),
+            None => (1, 1),
+        };
+
+        ParseError::Syntax {
+            line,
+            column,
+            message: format!("Expected {expected}, found end of input"),
+        }
+    }
+}
+
+fn unexpected(token: &Token, expected: &str) -> ParseError {
+    ParseError::Syntax {
+        line: token.line,
+        column: token.column,
+        message: format!("Expected {expected}, found {}", token.kind.describe()),
+    }
+}
+
+pub(crate) fn parse_bool(str: &str) -> Option<bool> {
+    match str.to_ascii_lowercase().as_str() {
+        "true" | "yes" => Some(true),
+        "false" | "no" => Some(false),
+        str => str.parse::<i64>().ok().map(|int| int != 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, ColorList, Label, Shape};
+
+    fn syntax_error(source: &str) -> (usize, usize) {
+        match parse(source) {
+            Err(ParseError::Syntax { line, column, .. }) => (line, column),
+            Err(error) => panic!("expected a syntax error, got {error}"),
+            Ok(_) => panic!("expected a syntax error"),
+        }
+    }
+
+    #[test]
+    fn header() {
+        let dot = parse("strict digraph G {}").unwrap();
+        assert!(dot.strict);
+        assert!(dot.directed);
+        assert_eq!(dot.graph.id.as_deref(), Some("G"));
+
+        let dot = parse("graph { }").unwrap();
+        assert!(!dot.strict);
+        assert!(!dot.directed);
+        assert_eq!(dot.graph.id, None);
+    }
+
+    #[test]
+    fn statements() {
+        let dot = parse(
+            r#"digraph {
+                rankdir=LR; foo=bar
+                a [label="A", shape=box]; b
+                subgraph cluster_x { c }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            dot.graph.attributes.as_slice(),
+            [
+                GraphAttribute::RankDir(crate::RankDir::LeftToRight),
+                GraphAttribute::Unknown("foo".into(), "bar".into()),
+            ]
+        );
+        assert_eq!(
+            dot.graph.nodes[0].attributes.as_slice(),
+            [
+                NodeAttribute::Label(Label::Text("A".into())),
+                NodeAttribute::Shape(Some(Shape::Box)),
+            ]
+        );
+        assert_eq!(dot.graph.nodes[1].id, "b");
+        assert_eq!(dot.graph.subgraphs[0].id.as_deref(), Some("x"));
+        assert!(dot.graph.subgraphs[0].is_cluster());
+
+        let dot = parse("graph { subgraph clusterFoo { a } subgraph clustering_x {} }").unwrap();
+        assert_eq!(dot.graph.subgraphs[0].id.as_deref(), Some("clusterFoo"));
+        assert!(dot.graph.subgraphs[0].is_cluster());
+        assert!(dot.graph.subgraphs[1].is_cluster());
+    }
+
+    #[test]
+    fn edge_chains() {
+        let dot = parse("digraph { a -> b -> {c d} [color=red] }").unwrap();
+
+        let subgraph = Graph::new(None).node(Node::new("c")).node(Node::new("d"));
+        let edge = Edge::chain([Endpoint::from("a"), "b".into(), subgraph.into()])
+            .color(crate::Color::Name("red".into()));
+        assert_eq!(dot.graph.edges, vec![edge]);
+    }
+
+    #[test]
+    fn ports() {
+        let dot = parse("digraph { a -> b:p:ne -> c:w -> d:x }").unwrap();
+
+        let edge = Edge::chain([
+            NodeRef::new("a"),
+            NodeRef::new("b").port("p").compass(CompassPoint::NorthEast),
+            NodeRef::new("c").compass(CompassPoint::West),
+            NodeRef::new("d").port("x"),
+        ]);
+        assert_eq!(dot.graph.edges, vec![edge]);
+
+        assert_eq!(syntax_error("digraph { a:p:x -> b }"), (1, 15));
+    }
+
+    #[test]
+    fn defaults() {
+        let dot = parse(
+            "graph { graph [compound=true] node [shape=box] a; edge [penwidth=2]; { edge [style=bold] } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            dot.graph.graph_defaults.as_slice(),
+            [GraphAttribute::Compound(true)]
+        );
+        assert_eq!(
+            dot.graph.node_defaults.as_slice(),
+            [NodeAttribute::Shape(Some(Shape::Box))]
+        );
+        assert_eq!(
+            dot.graph.edge_defaults.as_slice(),
+            [EdgeAttribute::PenWidth(2.0)]
+        );
+        assert!(dot.graph.nodes[0].attributes.is_empty());
+        assert_eq!(
+            dot.graph.subgraphs[0].edge_defaults.as_slice(),
+            [EdgeAttribute::Style(vec![crate::EdgeStyle::Bold])]
+        );
+    }
+
+    #[test]
+    fn defaults_in_statement_order() {
+        let dot = parse("digraph { a; node [shape=box]; b; a [color=red]; c -> a }").unwrap();
+
+        assert!(dot.graph.node_defaults.is_empty());
+        assert!(dot.graph.nodes[0].attributes.is_empty());
+        assert_eq!(
+            dot.graph.nodes[1].attributes.as_slice(),
+            [NodeAttribute::Shape(Some(Shape::Box))]
+        );
+
+        // `a` already existed, so it keeps its shape, while `c` is created by the edge
+        assert_eq!(
+            dot.graph.nodes[2].attributes.as_slice(),
+            [NodeAttribute::Color(ColorList::from(Color::Name(
+                "red".into()
+            )))]
+        );
+        assert_eq!(dot.graph.nodes[3].id, "c");
+        assert_eq!(
+            dot.graph.nodes[3].attributes.as_slice(),
+            [NodeAttribute::Shape(Some(Shape::Box))]
+        );
+
+        let dot = parse("graph { node [shape=box]; a; node [shape=circle]; b; a -- b; edge [style=bold]; { c -- d } }").unwrap();
+
+        assert_eq!(
+            dot.graph.node_defaults.as_slice(),
+            [NodeAttribute::Shape(Some(Shape::Box))]
+        );
+        assert!(dot.graph.nodes[0].attributes.is_empty());
+        assert_eq!(
+            dot.graph.nodes[1].attributes.as_slice(),
+            [NodeAttribute::Shape(Some(Shape::Circle))]
+        );
+        assert!(dot.graph.edge_defaults.is_empty());
+        assert!(dot.graph.edges[0].attributes.is_empty());
+
+        let subgraph = &dot.graph.subgraphs[0];
+        assert_eq!(
+            subgraph.nodes[0].attributes.as_slice(),
+            [NodeAttribute::Shape(Some(Shape::Circle))]
+        );
+        assert_eq!(
+            subgraph.edges[0].attributes.as_slice(),
+            [EdgeAttribute::Style(vec![crate::EdgeStyle::Bold])]
+        );
+    }
+
+    #[test]
+    fn html_and_concatenation() {
+        let dot = parse(r#"graph { a [label=<<b>A</b>>]; b [label="x" + "y"] }"#).unwrap();
+
+        assert_eq!(
+            dot.graph.nodes[0].attributes.as_slice(),
+            [NodeAttribute::Label(Label::HtmlLike("<<b>A</b>>".into()))]
+        );
+        assert_eq!(
+            dot.graph.nodes[1].attributes.as_slice(),
+            [NodeAttribute::Label(Label::Text("xy".into()))]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(syntax_error("graph {\n  a -> b\n}"), (2, 5));
+        assert_eq!(syntax_error("digraph {\n  a [label]\n}"), (2, 11));
+        assert_eq!(syntax_error("digraph { a"), (1, 11));
+        assert_eq!(syntax_error("node {}"), (1, 1));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{NodeAttribute, Shape};
+use std::ops::RangeInclusive;
+use thiserror::Error;
+
+/// A polygon node shape, written as `shape=polygon` along with the attributes that shape it
+///
+/// Only the parameters that were set are written, so Graphviz's defaults apply to the rest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolygonShape {
+    sides: u32,
+    skew: Option<f32>,
+    distortion: Option<f32>,
+    orientation: Option<f32>,
+    regular: Option<bool>,
+    peripheries: Option<u32>,
+}
+
+impl PolygonShape {
+    pub fn new(sides: u32) -> Self {
+        Self {
+            sides,
+            skew: None,
+            distortion: None,
+            orientation: None,
+            regular: None,
+            peripheries: None,
+        }
+    }
+
+    /// Slant the polygon, where positive values move the top to the right
+    pub fn skew(mut self, skew: f32) -> Self {
+        self.skew = Some(skew);
+        self
+    }
+
+    /// Widen the top of the polygon, or its bottom for negative values
+    pub fn distortion(mut self, distortion: f32) -> Self {
+        self.distortion = Some(distortion);
+        self
+    }
+
+    /// Rotate the polygon clockwise, in degrees
+    pub fn orientation(mut self, degrees: f32) -> Self {
+        self.orientation = Some(degrees);
+        self
+    }
+
+    /// Force the polygon to be regular, with equal sides and a height equal to its width
+    pub fn regular(mut self, regular: bool) -> Self {
+        self.regular = Some(regular);
+        self
+    }
+
+    /// The number of outlines drawn around the polygon
+    pub fn peripheries(mut self, peripheries: u32) -> Self {
+        self.peripheries = Some(peripheries);
+        self
+    }
+
+    /// Check that every parameter lies within the range Graphviz accepts
+    pub fn validate(&self) -> Result<(), PolygonError> {
+        if self.sides < MIN_SIDES {
+            return Err(PolygonError::TooFewSides(self.sides));
+        }
+
+        let checks = [
+            ("skew", self.skew, SKEW),
+            ("distortion", self.distortion, DISTORTION),
+            ("orientation", self.orientation, ORIENTATION),
+        ];
+        for (name, value, range) in checks {
+            match value {
+                Some(value) if !range.contains(&value) => {
+                    return Err(PolygonError::OutOfRange(name, value, range));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn attributes(&self) -> Vec<NodeAttribute> {
+        let mut attributes = vec![
+            NodeAttribute::Shape(Some(Shape::Polygon)),
+            NodeAttribute::Sides(self.sides),
+        ];
+        attributes.extend(self.skew.map(NodeAttribute::Skew));
+        attributes.extend(self.distortion.map(NodeAttribute::Distortion));
+        attributes.extend(self.orientation.map(NodeAttribute::Orientation));
+        attributes.extend(self.regular.map(NodeAttribute::Regular));
+        attributes.extend(self.peripheries.map(NodeAttribute::Peripheries));
+        attributes
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PolygonError {
+    #[error("A polygon needs at least {MIN_SIDES} sides, found {0}")]
+    TooFewSides(u32),
+
+    #[error("Polygon {0} must lie between {} and {}, found {1}", .2.start(), .2.end())]
+    OutOfRange(&'static str, f32, RangeInclusive<f32>),
+}
+
+/// Graphviz draws polygons with fewer sides as ellipses
+const MIN_SIDES: u32 = 3;
+
+const SKEW: RangeInclusive<f32> = -100.0..=100.0;
+const DISTORTION: RangeInclusive<f32> = -100.0..=100.0;
+const ORIENTATION: RangeInclusive<f32> = 0.0..=360.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation() {
+        assert_eq!(PolygonShape::new(5).skew(0.4).validate(), Ok(()));
+        assert_eq!(
+            PolygonShape::new(2).validate(),
+            Err(PolygonError::TooFewSides(2))
+        );
+        assert_eq!(
+            PolygonShape::new(4).distortion(-150.0).validate(),
+            Err(PolygonError::OutOfRange("distortion", -150.0, DISTORTION))
+        );
+        assert!(PolygonShape::new(4)
+            .orientation(f32::NAN)
+            .validate()
+            .is_err());
+        assert_eq!(
+            PolygonShape::new(4)
+                .orientation(400.0)
+                .validate()
+                .unwrap_err()
+                .to_string(),
+            "Polygon orientation must lie between 0 and 360, found 400"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+/// The label of a `record` or `Mrecord` shaped node
+///
+/// Fields are laid out next to each other, and every nested group flips the direction, so a
+/// group inside a horizontal record is vertical and vice versa.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordLabel {
+    pub fields: Vec<RecordField>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordField {
+    Text { port: Option<String>, text: String },
+    Group(RecordLabel),
+}
+
+impl RecordLabel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field(mut self, text: impl Into<String>) -> Self {
+        self.fields.push(RecordField::Text {
+            port: None,
+            text: text.into(),
+        });
+        self
+    }
+
+    /// A field that edges can connect to through `NodeRef::port`
+    pub fn port(mut self, port: impl Into<String>, text: impl Into<String>) -> Self {
+        self.fields.push(RecordField::Text {
+            port: Some(port.into()),
+            text: text.into(),
+        });
+        self
+    }
+
+    /// A nested group of fields, laid out in the opposite direction
+    pub fn group(mut self, group: RecordLabel) -> Self {
+        self.fields.push(RecordField::Group(group));
+        self
+    }
+
+    pub fn as_string(&self) -> String {
+        let mut string = String::from("\"");
+        self.write_fields(&mut string);
+        string.push('"');
+        string
+    }
+
+    fn write_fields(&self, string: &mut String) {
+        for (index, field) in self.fields.iter().enumerate() {
+            if index > 0 {
+                string.push('|');
+            }
+
+            match field {
+                RecordField::Text { port, text } => {
+                    if let Some(port) = port {
+                        string.push('<');
+                        escape(port, string);
+                        string.push('>');
+
+                        if !text.is_empty() {
+                            string.push(' ');
+                        }
+                    }
+
+                    escape(text, string);
+                }
+                RecordField::Group(group) => {
+                    string.push('{');
+                    group.write_fields(string);
+                    string.push('}');
+                }
+            }
+        }
+    }
+}
+
+/// Escape the characters that are special to records, and to the quoted string around them
+fn escape(text: &str, string: &mut String) {
+    for c in text.chars() {
+        match c {
+            '{' | '}' | '|' | '<' | '>' | ' ' | '\\' | '"' => {
+                string.push('\\');
+                string.push(c);
+            }
+            '\n' => string.push_str("\\n"),
+            c => string.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_string() {
+        let record = RecordLabel::new()
+            .port("f0", "left")
+            .port("f1", "mid dle")
+            .port("f2", "right");
+        assert_eq!(
+            record.as_string(),
+            r#""<f0> left|<f1> mid\ dle|<f2> right""#
+        );
+
+        let record = RecordLabel::new().field("hello\nworld").group(
+            RecordLabel::new()
+                .field("b")
+                .group(RecordLabel::new().field("c").port("here", "d").field("e"))
+                .field("f"),
+        );
+        assert_eq!(record.as_string(), r#""hello\nworld|{b|{c|<here> d|e}|f}""#);
+    }
+
+    #[test]
+    fn escaping() {
+        let record = RecordLabel::new()
+            .field("{a|b}")
+            .port("p", "")
+            .field("<\"x\\y\">");
+        assert_eq!(record.as_string(), r#""\{a\|b\}|<p>|\<\"x\\y\"\>""#);
+    }
+}
@@ -1,23 +1,136 @@
+use crate::Attribute;
 use std::{io, iter::ExactSizeIterator};
 
+/// Encode a string as a DOT `ID`
+///
+/// Identifiers and numerals are written as-is. Anything else, including the DOT keywords, is
+/// quoted, with quotes, backslashes and newlines escaped so the result is always a single `ID`.
 pub fn sanitize(str: &str) -> String {
-    let is_valid = match str.chars().next() {
-        Some(x) if x.is_numeric() => str.parse::<f32>().is_ok(),
-        Some(_) => str.chars().all(|c| c.is_alphanumeric() || c == '_'),
-        None => return String::new(),
-    };
-
-    if is_valid {
+    if is_identifier(str) || is_numeral(str) {
         str.to_string()
     } else {
-        format!("\"{str}\"")
+        quote(str)
+    }
+}
+
+fn quote(str: &str) -> String {
+    let mut quoted = String::with_capacity(str.len() + 2);
+    quoted.push('"');
+    for c in str.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Encode an escString, whose backslash escapes like `\l` and `\N` are meaningful to Graphviz
+///
+/// Unlike `sanitize`, backslashes are kept as they are. Only unescaped quotes, newlines and a
+/// trailing backslash are escaped, so the string stays a single `ID`.
+pub fn esc_string(str: &str) -> String {
+    if is_identifier(str) || is_numeral(str) {
+        return str.to_string();
+    }
+
+    let mut quoted = String::with_capacity(str.len() + 2);
+    quoted.push('"');
+
+    let mut chars = str.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\n') => quoted.push_str("\\\\\\n"),
+                Some(escaped) => {
+                    quoted.push('\\');
+                    quoted.push(escaped);
+                }
+                None => quoted.push_str("\\\\"),
+            },
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+pub fn bool_string(value: bool) -> String {
+    String::from(if value { "true" } else { "false" })
+}
+
+/// Join styles into the single comma-separated `style` value Graphviz expects
+pub fn styles_string<S>(styles: &[S], as_string: impl Fn(&S) -> String) -> String {
+    let styles: Vec<String> = styles.iter().map(as_string).collect();
+    sanitize(&styles.join(","))
+}
+
+/// Append styles to a `style` value, skipping the ones it already has
+pub fn add_styles<S: PartialEq>(existing: &mut Vec<S>, styles: impl IntoIterator<Item = S>) {
+    for style in styles {
+        if !existing.contains(&style) {
+            existing.push(style);
+        }
     }
 }
 
-pub fn write_attributes<'a, I, A: 'a>(attributes: I, mut w: impl io::Write) -> io::Result<()>
+/// Split a `style` value into its styles, failing if any of them is unknown
+pub fn parse_styles<S>(str: &str, from_name: impl Fn(&str) -> Option<S>) -> Option<Vec<S>> {
+    str.split(',')
+        .map(|style| from_name(style.trim()))
+        .collect()
+}
+
+/// Parse the width out of the `setlinewidth(n)` style
+pub fn parse_line_width(style: &str) -> Option<f32> {
+    style
+        .strip_prefix("setlinewidth(")?
+        .strip_suffix(')')?
+        .trim()
+        .parse()
+        .ok()
+}
+
+const KEYWORDS: [&str; 6] = ["node", "edge", "graph", "digraph", "subgraph", "strict"];
+
+fn is_identifier(str: &str) -> bool {
+    let mut chars = str.chars();
+    let valid = match chars.next() {
+        Some(c) => c == '_' || c.is_alphabetic(),
+        None => return false,
+    };
+
+    valid
+        && chars.all(|c| c == '_' || c.is_ascii_digit() || c.is_alphanumeric())
+        && !KEYWORDS
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(str))
+}
+
+/// Whether a string matches DOT's numeral grammar: `[-]?(.[0-9]+ | [0-9]+(.[0-9]*)?)`
+fn is_numeral(str: &str) -> bool {
+    let unsigned = str.strip_prefix('-').unwrap_or(str);
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+
+    let is_digits = |str: &str| str.chars().all(|c| c.is_ascii_digit());
+    let digit_count = integer.len() + fraction.map_or(0, str::len);
+
+    is_digits(integer) && fraction.is_none_or(is_digits) && digit_count > 0
+}
+
+pub fn write_attributes<'a, I, A>(attributes: I, mut w: impl io::Write) -> io::Result<()>
 where
     I: ExactSizeIterator<Item = &'a A>,
-    A: Attribute,
+    A: Attribute + 'a,
 {
     write!(w, " [")?;
 
@@ -37,10 +150,64 @@ where
 
 pub fn write_attribute(attribute: &impl Attribute, mut w: impl io::Write) -> io::Result<()> {
     let (key, value) = attribute.pair();
-    write!(w, "{}={}", key, &value)?;
+    write!(w, "{}={}", sanitize(key), &value)?;
     Ok(())
 }
 
-pub trait Attribute {
-    fn pair(&self) -> (&str, String);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{Id, Lexer, TokenKind};
+    use proptest::prelude::*;
+
+    #[test]
+    fn sanitize_ids() {
+        assert_eq!(sanitize("a_1"), "a_1");
+        assert_eq!(sanitize("-.5"), "-.5");
+        assert_eq!(sanitize("1."), "1.");
+        assert_eq!(sanitize("héllo"), "héllo");
+        assert_eq!(sanitize(""), "\"\"");
+        assert_eq!(sanitize("a b"), "\"a b\"");
+        assert_eq!(sanitize("1a"), "\"1a\"");
+    }
+
+    #[test]
+    fn sanitize_keywords() {
+        assert_eq!(sanitize("node"), "\"node\"");
+        assert_eq!(sanitize("Edge"), "\"Edge\"");
+        assert_eq!(sanitize("STRICT"), "\"STRICT\"");
+        assert_eq!(sanitize("nodes"), "nodes");
+    }
+
+    #[test]
+    fn sanitize_non_numerals() {
+        assert_eq!(sanitize("1e5"), "\"1e5\"");
+        assert_eq!(sanitize("inf"), "inf");
+        assert_eq!(sanitize("-inf"), "\"-inf\"");
+        assert_eq!(sanitize("NaN"), "NaN");
+        assert_eq!(sanitize("-"), "\"-\"");
+        assert_eq!(sanitize("."), "\".\"");
+    }
+
+    #[test]
+    fn sanitize_escapes() {
+        assert_eq!(sanitize("say \"hi\""), r#""say \"hi\"""#);
+        assert_eq!(sanitize("a\\"), r#""a\\""#);
+        assert_eq!(sanitize("a\nb"), r#""a\nb""#);
+    }
+
+    proptest! {
+        #[test]
+        fn sanitize_is_a_single_id(string in any::<String>()) {
+            let tokens = Lexer::new(&sanitize(&string)).tokenize().unwrap();
+            prop_assert_eq!(tokens.len(), 1);
+
+            match &tokens[0].kind {
+                TokenKind::Id(id @ (Id::Plain(_) | Id::Quoted(_))) => {
+                    prop_assert_eq!(id.unescaped(), string)
+                }
+                kind => prop_assert!(false, "unexpected token {:?}", kind),
+            }
+        }
+    }
 }
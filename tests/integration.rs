@@ -1,4 +1,10 @@
-use ellipsis::{Dot, Edge, Graph, Node, Shape};
+use ellipsis::{
+    sanitize, Arrow, ArrowPrimitive, ArrowShape, Attribute, AttributeValue, ClusterStyle, Color,
+    ColorList, ColorParseError, ColorScheme, CompassPoint, Direction, Dot, Edge, EdgeAttribute,
+    EdgeStyle, FixedSize, Graph, GraphAttribute, Label, LabelJust, LabelLoc, Node, NodeAttribute,
+    NodeRef, NodeStyle, Point, PolygonError, PolygonShape, Port, Rank, RankDir, RecordLabel, Rect,
+    Shape, Size, Splines, SubgraphError, Unit, UnknownNameError, Viewport,
+};
 use indoc::indoc;
 
 fn compare(dot: &Dot, expected: &str) {
@@ -7,6 +13,14 @@ fn compare(dot: &Dot, expected: &str) {
     assert_eq!(written, expected);
 }
 
+/// Compare the written graph, and check that parsing it back gives the same graph
+fn compare_round_trip(dot: &Dot, expected: &str) {
+    compare(dot, expected);
+
+    let parsed = expected.parse::<Dot>().unwrap();
+    assert_eq!(&parsed, dot);
+}
+
 #[test]
 fn empty() {
     let dot = Dot::new(false, Graph::new(Some("root".to_string())));
@@ -107,6 +121,27 @@ fn cluster() {
     compare(&dot, expected);
 }
 
+#[test]
+fn cluster_prefix() {
+    let source = indoc! {"
+        graph {
+          subgraph clusterFoo {
+            a
+          }
+          subgraph cluster_bar {
+            b
+          }
+        }"
+    };
+
+    let dot = source.parse::<Dot>().unwrap();
+    compare(&dot, source);
+
+    assert!(dot.graph.subgraphs.iter().all(Graph::is_cluster));
+    assert_eq!(dot.graph.subgraphs[0].id.as_deref(), Some("clusterFoo"));
+    assert_eq!(dot.graph.subgraphs[1].id.as_deref(), Some("bar"));
+}
+
 #[test]
 fn whitespace() {
     let dot = Dot::new(
@@ -132,3 +167,878 @@ fn whitespace() {
 
     compare(&dot, expected);
 }
+
+#[test]
+fn parse_round_trip() {
+    let source = indoc! {r##"
+        /* Written by hand */
+        digraph G {
+          subgraph cluster_sub {
+            c [label="C c"]
+          }
+
+          a [label=<<b>A</b>>, shape=box]
+          b
+
+          a -> b [color="#ff0000ff"]
+          b -> c
+
+          label=Example
+        }"##
+    };
+
+    let dot = source.parse::<Dot>().unwrap();
+    let expected = source.replace("/* Written by hand */\n", "");
+    compare(&dot, &expected);
+
+    let reparsed = Dot::parse(dot.write_to_string().unwrap().as_bytes()).unwrap();
+    assert_eq!(reparsed, dot);
+}
+
+#[test]
+fn parse_round_trip_escapes() {
+    let source = indoc! {r#"
+        digraph {
+          a [label="line1\lline2\l"]
+          b [label="\N in \G"]
+          c [label="<f0> left|<f1> mid\ dle", shape=record]
+          "C:\\dir" [tooltip="say \"hi\""]
+
+          a -> b [label="\E\n\\"]
+        }"#
+    };
+
+    let dot = source.parse::<Dot>().unwrap();
+    compare(&dot, source);
+
+    assert_eq!(dot.graph.nodes[3].id, r"C:\dir");
+    assert_eq!(
+        dot.graph.nodes[0].get("label"),
+        Some(&NodeAttribute::Label(Label::Text(r"line1\lline2\l".into())))
+    );
+}
+
+#[test]
+fn parse_round_trip_quoted_keys() {
+    let source = indoc! {r#"
+        graph {
+          a ["tool tip"=x]
+
+          "a b"=c
+          "\\"=ne
+          ""=x
+        }"#
+    };
+
+    let dot = source.parse::<Dot>().unwrap();
+    compare(&dot, source);
+
+    assert_eq!(
+        dot.graph.get("a b"),
+        Some(&GraphAttribute::Unknown("a b".into(), "c".into()))
+    );
+}
+
+#[test]
+fn parse_round_trip_subgraph_defaults() {
+    let source = "digraph { a [shape=box]; subgraph s { node [color=red]; a; b } }";
+    let dot = source.parse::<Dot>().unwrap();
+
+    let expected = indoc! {"
+        digraph {
+          subgraph s {
+            a
+            b [color=red]
+          }
+
+          a [shape=box]
+        }"
+    };
+
+    compare_round_trip(&dot, expected);
+}
+
+#[test]
+fn strict() {
+    let dot = Dot::new(true, Graph::new(None).edge(Edge::new("a", "b"))).strict();
+
+    let expected = indoc! {"
+        strict digraph {
+          a -> b
+        }"
+    };
+
+    compare(&dot, expected);
+}
+
+#[test]
+fn dedup_edges() {
+    let mut dot = Dot::new(
+        false,
+        Graph::new(None)
+            .subgraph(Graph::new(None).edge(Edge::new("a", "b").label("A")))
+            .edge(Edge::new("b", "a").pen_width(2.0))
+            .edge(Edge::new("b", "c"))
+            .edge(Edge::new("b", "c")),
+    )
+    .strict();
+    dot.dedup_edges();
+
+    let expected = indoc! {"
+        strict graph {
+          subgraph {
+            a -- b [label=A, penwidth=2]
+          }
+
+          b -- c
+        }"
+    };
+
+    compare(&dot, expected);
+}
+
+#[test]
+fn dedup_edge_chains() {
+    let mut dot = Dot::new(
+        true,
+        Graph::new(None)
+            .edge(Edge::chain(["a", "b", "c"]).label("chain"))
+            .edge(Edge::chain(["x", "y", "z"]))
+            .edge(Edge::new("a", "b").pen_width(2.0))
+            .edge(Edge::new("c", "b")),
+    )
+    .strict();
+    dot.dedup_edges();
+
+    let expected = indoc! {"
+        strict digraph {
+          a -> b [label=chain, penwidth=2]
+          b -> c [label=chain]
+          x -> y -> z
+          c -> b
+        }"
+    };
+
+    compare(&dot, expected);
+}
+
+#[test]
+fn dedup_edge_ports() {
+    let mut dot = "strict digraph { a:n -> b; a:s -> b; a -> b:w [color=red] }"
+        .parse::<Dot>()
+        .unwrap();
+    dot.dedup_edges();
+
+    let expected = indoc! {"
+        strict digraph {
+          a:s -> b:w [color=red]
+        }"
+    };
+
+    compare(&dot, expected);
+
+    let mut dot = "strict graph { a:n -- b; b:e -- a:s }"
+        .parse::<Dot>()
+        .unwrap();
+    dot.dedup_edges();
+
+    let expected = indoc! {"
+        strict graph {
+          a:s -- b:e
+        }"
+    };
+
+    compare(&dot, expected);
+}
+
+#[test]
+fn defaults() {
+    let dot = Dot::new(
+        true,
+        Graph::new(None)
+            .graph_defaults([GraphAttribute::Compound(true)])
+            .node_defaults([NodeAttribute::Shape(Some(Shape::Box))])
+            .subgraph(
+                Graph::new(Some("x".to_string()))
+                    .cluster()
+                    .edge_defaults([EdgeAttribute::Style(vec![EdgeStyle::Dashed])])
+                    .edge(Edge::new("a", "b")),
+            )
+            .node(Node::new("c")),
+    );
+
+    let expected = indoc! {"
+        digraph {
+          graph [compound=true]
+          node [shape=box]
+
+          subgraph cluster_x {
+            edge [style=dashed]
+
+            a -> b
+          }
+
+          c
+        }"
+    };
+
+    compare(&dot, expected);
+}
+
+#[test]
+fn edge_chain() {
+    let same_rank = Graph::new(None)
+        .attribute(GraphAttribute::Unknown("rank".into(), "same".into()))
+        .node(Node::new("c"))
+        .node(Node::new("d"));
+
+    let dot = Dot::new(
+        true,
+        Graph::new(None)
+            .edge(Edge::chain(["a", "b", "c"]).label("E"))
+            .edge(Edge::new("a", same_rank))
+            .edge(Edge::new(
+                Graph::new(None).node(Node::new("x").label("X")),
+                "z",
+            )),
+    );
+
+    let expected = indoc! {"
+        digraph {
+          a -> b -> c [label=E]
+          a -> {rank=same; c d}
+          subgraph {
+            x [label=X]
+          } -> z
+        }"
+    };
+
+    compare(&dot, expected);
+}
+
+#[test]
+fn record() {
+    let dot = Dot::new(
+        true,
+        Graph::new(None)
+            .node(
+                Node::new("a")
+                    .shape(Some(Shape::Record))
+                    .label(RecordLabel::new().port("f0", "x y").field("z")),
+            )
+            .edge(Edge::new(NodeRef::new("a").port("f0"), "b")),
+    );
+
+    let expected = indoc! {r#"
+        digraph {
+          a [shape=record, label="<f0> x\ y|z"]
+
+          a:f0 -> b
+        }"#
+    };
+
+    compare(&dot, expected);
+}
+
+#[test]
+fn layout_attributes() {
+    let dot = Dot::new(
+        true,
+        Graph::new(None)
+            .rank_dir(RankDir::LeftToRight)
+            .splines(Splines::Ortho)
+            .node_sep(0.5)
+            .size(Size::new(7.5, 10.0).fill())
+            .pad(Point::new(0.1, 0.2))
+            .font_name("Helvetica Neue")
+            .label_loc(LabelLoc::Top),
+    );
+
+    let expected = indoc! {r#"
+        digraph {
+          rankdir=LR
+          splines=ortho
+          nodesep=0.5
+          size="7.5,10!"
+          pad="0.1,0.2"
+          fontname="Helvetica Neue"
+          labelloc=t
+        }"#
+    };
+
+    compare_round_trip(&dot, expected);
+}
+
+#[test]
+fn node_attributes() {
+    let dot = Dot::new(
+        false,
+        Graph::new(None).node(
+            Node::new("a")
+                .width(1.5)
+                .fixed_size(FixedSize::Shape)
+                .margin(Point::new(0.1, 0.2))
+                .peripheries(2)
+                .xlabel("x")
+                .url("https://example.com/a")
+                .pos(Point::new(1.0, 2.0))
+                .pin(true),
+        ),
+    );
+
+    let expected = indoc! {r#"
+        graph {
+          a [width=1.5, fixedsize=shape, margin="0.1,0.2", peripheries=2, xlabel=x, URL="https://example.com/a", pos="1,2", pin=true]
+        }"#
+    };
+
+    compare_round_trip(&dot, expected);
+}
+
+#[test]
+fn edge_attributes() {
+    let dot = Dot::new(
+        true,
+        Graph::new(None).edge(
+            Edge::new("a", "b")
+                .weight(2.0)
+                .min_len(3)
+                .constraint(false)
+                .dir(Direction::Both)
+                .label_angle(-25.0)
+                .label_float(true)
+                .head_url("https://example.com/b")
+                .svg_id("e1"),
+        ),
+    );
+
+    let expected = indoc! {r#"
+        digraph {
+          a -> b [weight=2, minlen=3, constraint=false, dir=both, labelangle=-25, labelfloat=true, headURL="https://example.com/b", id=e1]
+        }"#
+    };
+
+    compare_round_trip(&dot, expected);
+}
+
+#[test]
+fn arrow_shapes() {
+    let dot = Dot::new(
+        true,
+        Graph::new(None).edge(
+            Edge::new("a", "b")
+                .arrow_head(
+                    ArrowShape::new([
+                        Arrow::new(ArrowPrimitive::Tee).left(),
+                        Arrow::new(ArrowPrimitive::Diamond).open().left(),
+                    ])
+                    .unwrap(),
+                )
+                .arrow_tail(ArrowPrimitive::Crow)
+                .dir(Direction::Both),
+        ),
+    );
+
+    let expected = indoc! {r#"
+        digraph {
+          a -> b [arrowhead=lteeoldiamond, arrowtail=crow, dir=both]
+        }"#
+    };
+
+    compare_round_trip(&dot, expected);
+}
+
+#[test]
+fn color_lists() {
+    let red = Color::Name("red".into());
+    let blue = Color::Name("blue".into());
+
+    let dot = Dot::new(
+        true,
+        Graph::new(None)
+            .node(
+                Node::new("a")
+                    .style(NodeStyle::Wedged)
+                    .fill_color(ColorList::new([(red.clone(), 0.3), (blue.clone(), 0.7)]).unwrap()),
+            )
+            .edge(Edge::new("a", "b").color(ColorList::new([red, blue]).unwrap())),
+    );
+
+    let expected = indoc! {r#"
+        digraph {
+          a [style=wedged, fillcolor="red;0.3:blue;0.7"]
+
+          a -> b [color="red:blue"]
+        }"#
+    };
+
+    compare_round_trip(&dot, expected);
+}
+
+#[test]
+fn color_schemes() {
+    let blues = ColorScheme::brewer("blues", 9).unwrap();
+
+    let dot = Dot::new(
+        true,
+        Graph::new(None)
+            .node(Node::new("a").color("crimson"))
+            .node(
+                Node::new("b")
+                    .color_scheme(blues)
+                    .color(Color::scheme(blues, "7").unwrap()),
+            )
+            .edge(Edge::new("a", "b").color("/svg/aqua")),
+    );
+
+    let expected = indoc! {r#"
+        digraph {
+          a [color=crimson]
+          b [colorscheme=blues9, color="/blues9/7"]
+
+          a -> b [color="/svg/aqua"]
+        }"#
+    };
+
+    compare_round_trip(&dot, expected);
+}
+
+#[test]
+fn fallible_colors() {
+    let node = Node::new("a")
+        .try_color("red")
+        .and_then(|node| node.try_fill_color("#ff000080;0.5:blue"))
+        .and_then(|node| node.try_font_color(Color::rgb(0, 0, 0)));
+    assert!(node.is_ok());
+    assert!(Node::new("a").try_color("invis").is_ok());
+    assert!(Edge::new("a", "b").try_color("none").is_ok());
+
+    assert!(Node::new("a")
+        .color_scheme(ColorScheme::Svg)
+        .try_color("aqua")
+        .is_ok());
+    assert!(Edge::new("a", "b")
+        .color_scheme(ColorScheme::brewer("blues", 9).unwrap())
+        .try_color("3")
+        .is_ok());
+
+    assert_eq!(
+        Node::new("a").try_color("blu ish"),
+        Err(ColorParseError::UnknownName("blu ish".into()))
+    );
+    assert_eq!(
+        Edge::new("a", "b").try_color("red;0.8:blue;0.8"),
+        Err(ColorParseError::FractionsExceedOne(1.6))
+    );
+    assert_eq!(
+        Graph::new(None).try_bg_color("rgb(1, 2)"),
+        Err(ColorParseError::InvalidCss("rgb(1, 2)".into()))
+    );
+}
+
+#[test]
+fn styles() {
+    let dot = Dot::new(
+        true,
+        Graph::new(None)
+            .subgraph(
+                Graph::new(Some("a".into()))
+                    .cluster()
+                    .styles([ClusterStyle::Filled, ClusterStyle::Rounded])
+                    .style(ClusterStyle::Filled)
+                    .node(Node::new("b")),
+            )
+            .node(
+                Node::new("c")
+                    .style(NodeStyle::Filled)
+                    .style(NodeStyle::Rounded)
+                    .styles([NodeStyle::Dashed, NodeStyle::Filled, NodeStyle::Dashed]),
+            )
+            .edge(Edge::new("b", "c").styles([
+                EdgeStyle::Bold,
+                EdgeStyle::SetLineWidth(2.5),
+                EdgeStyle::Bold,
+            ])),
+    );
+
+    let expected = indoc! {r#"
+        digraph {
+          subgraph cluster_a {
+            b
+
+            style="filled,rounded"
+          }
+
+          c [style="filled,rounded,dashed"]
+
+          b -> c [style="bold,setlinewidth(2.5)"]
+        }"#
+    };
+
+    compare_round_trip(&dot, expected);
+}
+
+#[test]
+#[allow(deprecated)]
+fn style_names() {
+    assert_eq!(NodeStyle::Invisible.as_str(), "invis");
+    assert_eq!(NodeStyle::SetLineWidth(2.0).as_str(), "setlinewidth");
+    assert_eq!(EdgeStyle::Tapered.as_str(), "tapered");
+    assert_eq!(EdgeStyle::SetLineWidth(2.0).as_string(), "setlinewidth(2)");
+}
+
+#[test]
+fn attribute_accessors() {
+    let mut node = Node::new("a")
+        .label("first")
+        .color("red")
+        .width(2.0)
+        .label("second");
+
+    assert_eq!(
+        node.get("label"),
+        Some(&NodeAttribute::Label(Label::Text("second".into())))
+    );
+
+    node.set(NodeAttribute::Color(Color::Name("blue".into()).into()));
+    assert_eq!(node.remove("width"), Some(NodeAttribute::Width(2.0)));
+
+    assert_eq!(node.attributes.len(), 2);
+
+    let dot = Dot::new(true, Graph::new(None).node(node));
+    let expected = indoc! {"
+        digraph {
+          a [label=second, color=blue]
+        }"
+    };
+
+    compare(&dot, expected);
+}
+
+#[test]
+fn custom_attributes() {
+    enum Extension {
+        Layer(u32),
+        Note(String),
+        Badge(String),
+    }
+
+    impl Attribute for Extension {
+        fn pair(&self) -> (&str, String) {
+            match self {
+                Self::Layer(layer) => ("x_layer", format!("{layer}")),
+                Self::Note(note) => ("x_note", sanitize(note)),
+                Self::Badge(html) => ("x_badge", format!("<{html}>")),
+            }
+        }
+    }
+
+    let dot = Dot::new(
+        true,
+        Graph::new(None)
+            .attr(Extension::Layer(1))
+            .node(
+                Node::new("a")
+                    .attr(Extension::Note("needs review".into()))
+                    .attr(Extension::Badge("<b>new</b>".into()))
+                    .attr(Extension::Note("done".into())),
+            )
+            .edge(Edge::new("a", "b").attr(Extension::Layer(2))),
+    );
+
+    let expected = indoc! {r#"
+        digraph {
+          a [x_note=done, x_badge=<<b>new</b>>]
+
+          a -> b [x_layer=2]
+
+          x_layer=1
+        }"#
+    };
+
+    compare(&dot, expected);
+}
+
+#[test]
+fn attribute_values() {
+    let dot = Dot::new(
+        true,
+        Graph::new(None).node(
+            Node::new("a")
+                .attribute(NodeAttribute::Unknown("pos".into(), "1,2!".into()))
+                .attribute(NodeAttribute::Unknown(
+                    "xlp".into(),
+                    Point::new(0.5, 1.0).into(),
+                ))
+                .attribute(NodeAttribute::Unknown("z".into(), 3.into()))
+                .attribute(NodeAttribute::Unknown(
+                    "comment".into(),
+                    AttributeValue::EscString(r"\N\l".into()),
+                ))
+                .attribute(NodeAttribute::Unknown(
+                    "note".into(),
+                    AttributeValue::Html("<b>x</b>".into()),
+                ))
+                .attribute(NodeAttribute::Unknown("source".into(), r"C:\dir".into())),
+        ),
+    );
+
+    let expected = indoc! {r#"
+        digraph {
+          a [pos="1,2!", xlp="0.5,1", z=3, comment="\N\l", note=<<b>x</b>>, source="C:\\dir"]
+        }"#
+    };
+
+    compare(&dot, expected);
+    compare(&expected.parse().unwrap(), expected);
+}
+
+#[test]
+fn geometry_attributes() {
+    let dot = Dot::new(
+        false,
+        Graph::new(None)
+            .bb(Rect::new((0.0, 0.0), (144.0, 72.0)))
+            .margin(Point::new(0.25, 0.5))
+            .page(Size::new(8.5, 11.0))
+            .viewport(Viewport::new(400.0, 300.0).zoom(2.0))
+            .subgraph(
+                Graph::new(Some("x".into()))
+                    .cluster()
+                    .margin(Point::new(8.0, 8.0).unit(Unit::Points))
+                    .node(Node::new("c")),
+            )
+            .subgraph(
+                Graph::new(Some("y".into()))
+                    .cluster()
+                    .graph_defaults([GraphAttribute::Margin(
+                        Point::new(0.5, 0.25).unit(Unit::Inches),
+                    )])
+                    .node(Node::new("d")),
+            )
+            .node(
+                Node::new("a")
+                    .pos(Point::new(1.0, 2.0).unit(Unit::Inches).fixed())
+                    .margin(Point::new(9.0, 18.0).unit(Unit::Points)),
+            )
+            .edge(Edge::new("a", "b").lp(Point::new(36.0, 36.0))),
+    );
+
+    let expected = indoc! {r#"
+        graph {
+          subgraph cluster_x {
+            c
+
+            margin="8,8"
+          }
+          subgraph cluster_y {
+            graph [margin="36,18"]
+
+            d
+          }
+
+          a [pos="72,144!", margin="0.125,0.25"]
+
+          a -- b [lp="36,36"]
+
+          bb="0,0,144,72"
+          margin="0.25,0.5"
+          page="8.5,11"
+          viewport="400,300,2"
+        }"#
+    };
+
+    compare(&dot, expected);
+    compare(&expected.parse().unwrap(), expected);
+}
+
+#[test]
+fn shapes() {
+    let dot = Dot::new(
+        false,
+        Graph::new(None)
+            .node(Node::new("a").shape(Some(Shape::Cylinder)))
+            .node(Node::new("b").shape(Some("Mrecord".parse().unwrap())))
+            .node(Node::new("c").shape(Some(Shape::Rectangle)))
+            .node(Node::new("d").shape(Some(Shape::LPromoter)))
+            .node(Node::new("e").shape(Some("blob".parse().unwrap())))
+            .node(Node::new("f").shape(None)),
+    );
+
+    let expected = indoc! {"
+        graph {
+          a [shape=cylinder]
+          b [shape=Mrecord]
+          c [shape=rectangle]
+          d [shape=lpromoter]
+          e [shape=blob]
+          f [shape=none]
+        }"
+    };
+
+    compare_round_trip(&dot, expected);
+}
+
+#[test]
+fn names_from_str() {
+    assert_eq!("doublecircle".parse(), Ok(Shape::DoubleCircle));
+    assert_eq!("blob".parse(), Ok(Shape::Unknown("blob".into())));
+    assert_eq!(Shape::TripleOctagon.to_string(), "tripleoctagon");
+    assert_eq!(Shape::Unknown("blob".into()).to_string(), "blob");
+
+    assert_eq!("invis".parse(), Ok(NodeStyle::Invisible));
+    assert_eq!("setlinewidth(2)".parse(), Ok(NodeStyle::SetLineWidth(2.0)));
+    assert_eq!(NodeStyle::Wedged.to_string(), "wedged");
+
+    assert_eq!("tapered".parse(), Ok(EdgeStyle::Tapered));
+    assert_eq!(EdgeStyle::Invisible.to_string(), "invis");
+    assert_eq!(
+        "wavy".parse::<EdgeStyle>(),
+        Err(UnknownNameError {
+            kind: "edge style",
+            name: "wavy".into()
+        })
+    );
+
+    assert_eq!("ne".parse(), Ok(CompassPoint::NorthEast));
+    assert_eq!(CompassPoint::Adjacent.to_string(), "_");
+    assert_eq!(
+        "up".parse::<CompassPoint>().unwrap_err().to_string(),
+        "Unknown compass point `up`"
+    );
+}
+
+#[test]
+fn polygon() {
+    let dot = Dot::new(
+        false,
+        Graph::new(None)
+            .node(
+                Node::new("a")
+                    .label("A")
+                    .polygon(PolygonShape::new(5).skew(0.4).peripheries(2)),
+            )
+            .node(
+                Node::new("b").polygon(
+                    PolygonShape::new(7)
+                        .distortion(-0.5)
+                        .orientation(30.0)
+                        .regular(true),
+                ),
+            ),
+    );
+
+    let expected = indoc! {"
+        graph {
+          a [label=A, shape=polygon, sides=5, skew=0.4, peripheries=2]
+          b [shape=polygon, sides=7, distortion=-0.5, orientation=30, regular=true]
+        }"
+    };
+
+    compare_round_trip(&dot, expected);
+
+    assert_eq!(
+        Node::new("c").try_polygon(PolygonShape::new(1)),
+        Err(PolygonError::TooFewSides(1))
+    );
+}
+
+#[test]
+fn cluster_attributes() {
+    let dot = Dot::new(
+        true,
+        Graph::new(None)
+            .rank_dir(RankDir::LeftToRight)
+            .subgraph(
+                Graph::new(Some("a".to_string()))
+                    .cluster()
+                    .label("Cluster")
+                    .label_just(LabelJust::Left)
+                    .label_loc(LabelLoc::Bottom)
+                    .styles([ClusterStyle::Rounded, ClusterStyle::Filled])
+                    .fill_color("lightgrey")
+                    .pen_color("blue")
+                    .pen_width(2.0)
+                    .peripheries(2)
+                    .tooltip("The first cluster")
+                    .url("https://example.com")
+                    .node(Node::new("a")),
+            )
+            .subgraph(
+                Graph::new(None)
+                    .rank(Rank::Same)
+                    .node(Node::new("b"))
+                    .node(Node::new("c")),
+            ),
+    );
+
+    let expected = indoc! {r#"
+        digraph {
+          subgraph cluster_a {
+            a
+
+            label=Cluster
+            labeljust=l
+            labelloc=b
+            style="rounded,filled"
+            fillcolor=lightgrey
+            pencolor=blue
+            penwidth=2
+            peripheries=2
+            tooltip="The first cluster"
+            URL="https://example.com"
+          }
+          subgraph {
+            b
+            c
+
+            rank=same
+          }
+
+          rankdir=LR
+        }"#
+    };
+
+    compare_round_trip(&dot, expected);
+
+    assert_eq!(
+        Graph::new(None).try_subgraph(Graph::new(None).cluster().rank_dir(RankDir::TopToBottom)),
+        Err(SubgraphError::RootOnlyAttribute("rankdir".to_string()))
+    );
+    assert_eq!(
+        Graph::new(None)
+            .try_subgraph(Graph::new(None).graph_defaults([GraphAttribute::NodeSep(1.0)]))
+            .unwrap_err()
+            .to_string(),
+        "`nodesep` only applies to the root graph, not to subgraphs"
+    );
+
+    let graph = Graph::new(None).subgraph(Graph::new(None).rank_dir(RankDir::TopToBottom));
+    assert_eq!(
+        graph.subgraphs[0].get("rankdir"),
+        Some(&GraphAttribute::RankDir(RankDir::TopToBottom))
+    );
+}
+
+#[test]
+fn edge_ports() {
+    let dot = Dot::new(
+        true,
+        Graph::new(None)
+            .edge(
+                Edge::new("a", "b")
+                    .tail_port(CompassPoint::South)
+                    .head_port(Port::new("f0").compass(CompassPoint::North)),
+            )
+            .edge(Edge::new("b", "c").head_port("f1")),
+    );
+
+    let expected = indoc! {r#"
+        digraph {
+          a -> b [tailport=s, headport="f0:n"]
+          b -> c [headport=f1]
+        }"#
+    };
+
+    compare_round_trip(&dot, expected);
+}